@@ -8,17 +8,25 @@ fn main() {
     tonic_build::configure()
         .out_dir("src/pb")
         .with_sql_type(&["reservation.ReservationStatus"])
+        .with_serde(&["reservation.OpeningHours", "reservation.Attendee"])
         .with_builder(&[
             "reservation.ReservationQuery",
             "reservation.ReservationFilter",
         ])
         .with_builder_into(
             "reservation.ReservationQuery",
-            &["resource_id", "user_id", "status", "page", "desc"],
+            &[
+                "resource_id",
+                "user_id",
+                "status",
+                "page",
+                "desc",
+                "metadata",
+            ],
         )
         .with_builder_into(
             "reservation.ReservationFilter",
-            &["resource_id", "user_id", "status", "desc"],
+            &["resource_id", "user_id", "status", "desc", "metadata"],
         )
         .with_builder_attributes(&["reservation.ReservationQuery"], &["start", "end"])
         // .with_builder_option("reservation.ReservationFilter", &["cursor"])
@@ -41,8 +49,10 @@ fn main() {
 /// 为 tonic_build::Builder 添加扩展方法,用于设置属性
 trait BuilderExt {
     fn with_sql_type(self, paths: &[&str]) -> Self;
+    fn with_serde(self, paths: &[&str]) -> Self;
     fn with_builder(self, paths: &[&str]) -> Self;
     fn with_builder_into(self, path: &str, fields: &[&str]) -> Self;
+    #[allow(dead_code)]
     fn with_builder_option(self, path: &str, fields: &[&str]) -> Self;
     fn with_field_attribute(self, path: &str, fields: &[&str], attribute: &str) -> Self;
     fn with_builder_attributes(self, path: &[&str], attribute: &[&str]) -> Self;
@@ -57,6 +67,13 @@ impl BuilderExt for Builder {
         })
     }
 
+    // 存储为 jsonb 的类型需要 serde
+    fn with_serde(self, paths: &[&str]) -> Self {
+        paths.iter().fold(self, |acc, path| {
+            acc.type_attribute(path, "#[derive(serde::Serialize, serde::Deserialize)]")
+        })
+    }
+
     fn with_builder(self, paths: &[&str]) -> Self {
         paths.iter().fold(self, |acc, path| {
             acc.type_attribute(path, "#[derive(derive_builder::Builder)]")
@@ -0,0 +1,19 @@
+#![no_main]
+
+use abi::{ReservationConflict, ReservationConflictInfo};
+use libfuzzer_sys::fuzz_target;
+
+// the detail of a database error must never panic the parser
+fuzz_target!(|data: &[u8]| {
+    if let Ok(s) = std::str::from_utf8(data) {
+        let info = ReservationConflictInfo::from(s);
+        let parsed = s.parse::<ReservationConflict>().ok();
+        match info {
+            ReservationConflictInfo::Parsed(conflict) => assert_eq!(Some(*conflict), parsed),
+            ReservationConflictInfo::Unparsed(detail) => {
+                assert_eq!(detail, s);
+                assert!(parsed.is_none());
+            }
+        }
+    }
+});
@@ -1,43 +1,102 @@
 use chrono::{DateTime, Utc};
-use regex::Regex;
 use std::{collections::HashMap, convert::Infallible, str::FromStr};
-/// TODO: write a parser
+
+use crate::ReservationId;
+
+/// a conflict reported by an exclusion constraint, unparsed if the detail of the database
+/// error couldn't be understood
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ReservationConflictInfo {
-    Parsed(ReservationConflict),
+    Parsed(Box<ReservationConflict>),
     Unparsed(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ReservationConflict {
+    pub kind: ConflictKind,
     pub old: ReservationWindow,
     pub new: ReservationWindow,
+    /// every existing reservation the new one conflicts with, old among them.
+    /// filled in by the caller, the database only reports one
+    pub overlapping: Vec<ReservationWindow>,
+    /// free alternatives for the new one, only filled in if asked for
+    pub suggestions: Vec<ReservationWindow>,
+}
+
+/// why two reservations conflict
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConflictKind {
+    /// the reservation windows overlap
+    #[default]
+    Overlap,
+    /// the windows only overlap once the buffers of the resource are added,
+    /// the windows of the conflict include the buffers then
+    Buffer,
+    /// the user already holds an overlapping reservation on a resource of a user exclusive type
+    User,
+}
+
+/// conflict of one item in a batch reservation, index is the position in the request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchConflict {
+    pub index: usize,
+    pub info: ReservationConflictInfo,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ReservationWindow {
-    /// resource id
+    /// reservation id, 0 if unknown
+    pub id: ReservationId,
+    /// resource id, empty if the conflict doesn't name it
     pub rid: String,
-    /// start time
-    pub start: DateTime<Utc>,
-    /// end time
-    pub end: DateTime<Utc>,
+    /// user id, empty if the conflict doesn't name it
+    pub uid: String,
+    /// start time, None if unbounded
+    pub start: Option<DateTime<Utc>>,
+    /// end time, None if unbounded
+    pub end: Option<DateTime<Utc>>,
+    /// IANA time zone of the reservation, empty if unknown
+    pub time_zone: String,
 }
 
+/// a bound of a range, None if unbounded
+type Bound = Option<DateTime<Utc>>;
+
+/// the keys of the constraint, with the values of the new and the existing row
 struct ParsedInfo {
+    keys: Vec<String>,
     old: HashMap<String, String>,
     new: HashMap<String, String>,
 }
 
+impl ReservationConflictInfo {
+    pub fn set_kind(&mut self, kind: ConflictKind) {
+        if let Self::Parsed(conflict) = self {
+            conflict.kind = kind;
+        }
+    }
+
+    pub fn set_suggestions(&mut self, suggestions: Vec<ReservationWindow>) {
+        if let Self::Parsed(conflict) = self {
+            conflict.suggestions = suggestions;
+        }
+    }
+}
+
+impl From<&str> for ReservationConflictInfo {
+    fn from(s: &str) -> Self {
+        match s.parse() {
+            Ok(conflict) => Self::Parsed(Box::new(conflict)),
+            Err(_) => Self::Unparsed(s.to_string()),
+        }
+    }
+}
+
 impl FromStr for ReservationConflictInfo {
     type Err = Infallible;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if let Ok(conflict) = s.parse() {
-            Ok(Self::Parsed(conflict))
-        } else {
-            Ok(Self::Unparsed(s.to_string()))
-        }
+        Ok(s.into())
     }
 }
 
@@ -53,9 +112,13 @@ impl TryFrom<ParsedInfo> for ReservationConflict {
     type Error = ();
 
     fn try_from(info: ParsedInfo) -> Result<Self, Self::Error> {
+        let range = range_key(&info.keys, &info.new).ok_or(())?;
         Ok(Self {
-            old: info.old.try_into()?,
-            new: info.new.try_into()?,
+            kind: ConflictKind::default(),
+            old: ReservationWindow::from_values(&info.old, &range)?,
+            new: ReservationWindow::from_values(&info.new, &range)?,
+            overlapping: vec![],
+            suggestions: vec![],
         })
     }
 }
@@ -64,46 +127,202 @@ impl TryFrom<HashMap<String, String>> for ReservationWindow {
     type Error = ();
 
     fn try_from(info: HashMap<String, String>) -> Result<Self, Self::Error> {
-        // "timespan": "\"2023-01-02 17:10:10+00\",\"2023-01-05 17:10:10+00\""
-        // 把 " 过滤
-        let timespan_str = info.get("timespan").ok_or(())?.replace('"', "");
-        let mut split = timespan_str.splitn(2, ',');
-        let start = parse_time(split.next().ok_or(())?)?;
-        println!("{:?}", start);
-        let end = parse_time(split.next().ok_or(())?)?;
+        let mut keys: Vec<String> = info.keys().cloned().collect();
+        keys.sort();
+        let range = range_key(&keys, &info).ok_or(())?;
+        Self::from_values(&info, &range)
+    }
+}
+
+impl ReservationWindow {
+    fn from_values(values: &HashMap<String, String>, range: &str) -> Result<Self, ()> {
+        let (start, end) = parse_range(values.get(range).ok_or(())?)?;
+        let rid = values.get("resource_id");
+        let uid = values.get("user_id");
+        if rid.is_none() && uid.is_none() {
+            return Err(());
+        }
         Ok(Self {
-            rid: info.get("resource_id").ok_or(())?.to_string(),
+            id: 0,
+            rid: rid.cloned().unwrap_or_default(),
+            uid: uid.cloned().unwrap_or_default(),
             start,
             end,
+            time_zone: String::new(),
         })
     }
 }
 
+/// the key holding the window: timespan, buffered_timespan or else the last range valued key
+fn range_key(keys: &[String], values: &HashMap<String, String>) -> Option<String> {
+    ["timespan", "buffered_timespan"]
+        .iter()
+        .map(|k| k.to_string())
+        .find(|k| values.contains_key(k))
+        .or_else(|| {
+            keys.iter()
+                .rev()
+                .find(|k| values.get(*k).is_some_and(|v| parse_range(v).is_ok()))
+                .cloned()
+        })
+}
+
 /// Key (resource_id, timespan)=(test-resource, [\"2023-01-02 17:10:10+00\",\"2023-01-05 17:10:10+00\")) conflicts with existing key (resource_id, timespan)=(test-resource, [\"2023-01-01 17:10:10+00\",\"2023-01-04 17:10:10+00\")).
+///
+/// postgres prints the values without quoting them, so a value containing ", " makes
+/// the detail ambiguous and fails to parse, callers have to look the rows up then
 impl FromStr for ParsedInfo {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let re = Regex::new(r#"\((?P<k1>[a-zA-Z0-9_-]+)\s*,\s*(?P<k2>[a-zA-Z0-9_-]+)\)=\((?P<v1>[a-zA-Z0-9_-]+)\s*,\s*\[(?P<v2>[^\)\]]+)"#).unwrap();
-        let mut maps = vec![];
-        for cap in re.captures_iter(s) {
-            let mut map = HashMap::new();
-            map.insert(cap["k1"].to_string(), cap["v1"].to_string());
-            map.insert(cap["k2"].to_string(), cap["v2"].to_string());
-            maps.push(map);
-        }
-        if maps.len() != 2 {
+        let rest = s.trim().strip_prefix("Key (").ok_or(())?;
+        let keys_len = closing_paren(rest).ok_or(())?;
+        let keys_str = &rest[..keys_len];
+        let rest = rest[keys_len..].strip_prefix(")=(").ok_or(())?;
+
+        // the existing key repeats the key list, so it marks where the new values end
+        let separator = format!(") conflicts with existing key ({})=(", keys_str);
+        let (new, old) = rest.split_once(&separator).ok_or(())?;
+        let old = old
+            .strip_suffix(").")
+            .or_else(|| old.strip_suffix(')'))
+            .ok_or(())?;
+
+        let keys: Vec<String> = split_values(keys_str).into_iter().map(unquote).collect();
+        if keys.iter().any(|k| k.is_empty()) {
             return Err(());
         }
         Ok(Self {
-            new: maps[0].clone(),
-            old: maps[1].clone(),
+            new: zip_values(&keys, new)?,
+            old: zip_values(&keys, old)?,
+            keys,
         })
     }
 }
 
+fn zip_values(keys: &[String], values: &str) -> Result<HashMap<String, String>, ()> {
+    let values = split_values(values);
+    if values.len() != keys.len() {
+        return Err(());
+    }
+    Ok(keys
+        .iter()
+        .cloned()
+        .zip(values.into_iter().map(unquote))
+        .collect())
+}
+
+/// position of the parenthesis closing the list s starts in
+fn closing_paren(s: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut quoted = false;
+    let mut escaped = false;
+    for (i, c) in s.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            '(' | '[' if !quoted => depth += 1,
+            ')' | ']' if !quoted => {
+                if depth == 0 {
+                    return Some(i);
+                }
+                depth -= 1;
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// split a list of keys or values on the ", " outside of quotes and brackets
+fn split_values(s: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut quoted = false;
+    let mut escaped = false;
+    let mut start = 0;
+    let mut chars = s.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            '(' | '[' if !quoted => depth += 1,
+            ')' | ']' if !quoted => depth = depth.saturating_sub(1),
+            ',' if !quoted && depth == 0 && matches!(chars.peek(), Some((_, ' '))) => {
+                parts.push(&s[start..i]);
+                chars.next();
+                start = i + 2;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+}
+
+/// strip the quotes around a value, \x and "" inside of them stand for x and "
+fn unquote(s: &str) -> String {
+    let inner = match s.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+        Some(inner) if s.len() >= 2 => inner,
+        _ => return s.to_string(),
+    };
+    let mut ret = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' | '"' => {
+                if let Some(next) = chars.next() {
+                    ret.push(next);
+                }
+            }
+            _ => ret.push(c),
+        }
+    }
+    ret
+}
+
+/// ["2023-01-02 17:10:10+00","2023-01-05 17:10:10+00"), a missing or infinite bound is None
+fn parse_range(s: &str) -> Result<(Bound, Bound), ()> {
+    let inner = s
+        .strip_prefix(['[', '('])
+        .and_then(|s| s.strip_suffix([']', ')']))
+        .ok_or(())?;
+
+    let mut quoted = false;
+    let mut escaped = false;
+    let mut split = None;
+    for (i, c) in inner.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            ',' if !quoted => {
+                if split.is_some() {
+                    return Err(());
+                }
+                split = Some(i);
+            }
+            _ => {}
+        }
+    }
+    let split = split.ok_or(())?;
+    Ok((
+        parse_bound(&inner[..split])?,
+        parse_bound(&inner[split + 1..])?,
+    ))
+}
+
+fn parse_bound(s: &str) -> Result<Bound, ()> {
+    match unquote(s).as_str() {
+        "" | "infinity" | "-infinity" => Ok(None),
+        s => parse_time(s).map(Some),
+    }
+}
+
 fn parse_time(s: &str) -> Result<DateTime<Utc>, ()> {
-    Ok(DateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%#z")
+    Ok(DateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f%#z")
         .map_err(|_| ())?
         .with_timezone(&Utc))
 }
@@ -111,6 +330,7 @@ fn parse_time(s: &str) -> Result<DateTime<Utc>, ()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     const S: &str = "Key (resource_id, timespan)=(test-resource, [\"2023-01-02 17:10:10+00\",\"2023-01-05 17:10:10+00\")) conflicts with existing key (resource_id, timespan)=(test-resource, [\"2023-01-01 17:10:10+00\",\"2023-01-04 17:10:10+00\")).";
 
@@ -133,24 +353,12 @@ mod tests {
         let new_window = ReservationWindow::try_from(info.new).unwrap();
         let old_window = ReservationWindow::try_from(info.old).unwrap();
         assert_eq!(new_window.rid, "test-resource".to_string());
-        assert_eq!(
-            new_window.start,
-            parse_time("2023-01-02 17:10:10+00").unwrap()
-        );
-        assert_eq!(
-            new_window.end,
-            parse_time("2023-01-05 17:10:10+00").unwrap()
-        );
+        assert_eq!(new_window.start, parse_time("2023-01-02 17:10:10+00").ok());
+        assert_eq!(new_window.end, parse_time("2023-01-05 17:10:10+00").ok());
 
         assert_eq!(old_window.rid, "test-resource".to_string());
-        assert_eq!(
-            old_window.start,
-            parse_time("2023-01-01 17:10:10+00").unwrap()
-        );
-        assert_eq!(
-            old_window.end,
-            parse_time("2023-01-04 17:10:10+00").unwrap()
-        );
+        assert_eq!(old_window.start, parse_time("2023-01-01 17:10:10+00").ok());
+        assert_eq!(old_window.end, parse_time("2023-01-04 17:10:10+00").ok());
     }
 
     #[test]
@@ -161,22 +369,39 @@ mod tests {
 
         assert_eq!(
             rsvp_cft.new.start,
-            parse_time("2023-01-02 17:10:10+00").unwrap()
-        );
-        assert_eq!(
-            rsvp_cft.new.end,
-            parse_time("2023-01-05 17:10:10+00").unwrap()
+            parse_time("2023-01-02 17:10:10+00").ok()
         );
+        assert_eq!(rsvp_cft.new.end, parse_time("2023-01-05 17:10:10+00").ok());
 
         assert_eq!(rsvp_cft.old.rid, "test-resource".to_string());
         assert_eq!(
             rsvp_cft.old.start,
-            parse_time("2023-01-01 17:10:10+00").unwrap()
-        );
-        assert_eq!(
-            rsvp_cft.old.end,
-            parse_time("2023-01-04 17:10:10+00").unwrap()
+            parse_time("2023-01-01 17:10:10+00").ok()
         );
+        assert_eq!(rsvp_cft.old.end, parse_time("2023-01-04 17:10:10+00").ok());
+    }
+
+    #[test]
+    fn from_str_with_more_keys_should_work() {
+        let s = "Key (tenant_id, resource_id, timespan)=(acme, room-1, [\"2023-01-02 17:10:10+00\",\"2023-01-05 17:10:10+00\")) conflicts with existing key (tenant_id, resource_id, timespan)=(acme, room-1, [\"2023-01-01 17:10:10+00\",\"2023-01-04 17:10:10+00\")).";
+        let info = ParsedInfo::from_str(s).unwrap();
+        assert_eq!(info.new.get("tenant_id").unwrap(), "acme");
+
+        let conflict = ReservationConflict::from_str(s).unwrap();
+        assert_eq!(conflict.old.rid, "room-1".to_string());
+        assert_eq!(conflict.old.end, parse_time("2023-01-04 17:10:10+00").ok());
+    }
+
+    #[test]
+    fn from_str_with_user_key_should_work() {
+        let s = "Key (tenant_id, user_id, timespan)=(acme, alice, [\"2023-01-02 17:10:10+00\",\"2023-01-05 17:10:10+00\")) conflicts with existing key (tenant_id, user_id, timespan)=(acme, alice, [\"2023-01-01 17:10:10+00\",\"2023-01-04 17:10:10+00\")).";
+        let conflict = ReservationConflict::from_str(s).unwrap();
+        assert_eq!(conflict.new.uid, "alice".to_string());
+        assert_eq!(conflict.old.uid, "alice".to_string());
+        assert!(conflict.old.rid.is_empty());
+
+        let s = "Key (tenant_id, timespan)=(acme, [\"2023-01-02 17:10:10+00\",\"2023-01-05 17:10:10+00\")) conflicts with existing key (tenant_id, timespan)=(acme, [\"2023-01-01 17:10:10+00\",\"2023-01-04 17:10:10+00\")).";
+        assert!(ReservationConflict::from_str(s).is_err());
     }
 
     #[test]
@@ -184,4 +409,115 @@ mod tests {
         let rsvp_cft_info = ReservationConflictInfo::from_str(S).unwrap();
         assert!(matches!(rsvp_cft_info, ReservationConflictInfo::Parsed(..)));
     }
+
+    #[test]
+    fn from_str_with_spaces_and_quotes_should_work() {
+        let s = "Key (tenant_id, resource_id, timespan)=(acme, room 1, [\"2023-01-02 17:10:10.5+05:30\",\"2023-01-05 17:10:10+00\")) conflicts with existing key (tenant_id, resource_id, timespan)=(acme, \"room, \\\"a\\\"\", [\"2023-01-01 17:10:10+00\",\"2023-01-04 17:10:10+00\")).";
+        let conflict = ReservationConflict::from_str(s).unwrap();
+        assert_eq!(conflict.new.rid, "room 1");
+        assert_eq!(conflict.old.rid, "room, \"a\"");
+        assert_eq!(
+            conflict.new.start,
+            parse_time("2023-01-02 11:40:10.5+00").ok()
+        );
+    }
+
+    #[test]
+    fn from_str_with_unbounded_range_should_work() {
+        let s = "Key (resource_id, timespan)=(room-1, [\"2023-01-02 17:10:10+00\",)) conflicts with existing key (resource_id, timespan)=(room-1, (,infinity)).";
+        let conflict = ReservationConflict::from_str(s).unwrap();
+        assert_eq!(
+            conflict.new.start,
+            parse_time("2023-01-02 17:10:10+00").ok()
+        );
+        assert_eq!(conflict.new.end, None);
+        assert_eq!(conflict.old.start, None);
+        assert_eq!(conflict.old.end, None);
+    }
+
+    #[test]
+    fn from_str_with_any_constraint_shape_should_work() {
+        // expression keys and a range column of another name
+        let s = "Key (lower(resource_id), during)=(room-1, [\"2023-01-02 17:10:10+00\",\"2023-01-05 17:10:10+00\")) conflicts with existing key (lower(resource_id), during)=(room-1, [\"2023-01-01 17:10:10+00\",\"2023-01-04 17:10:10+00\")).";
+        let info = ParsedInfo::from_str(s).unwrap();
+        assert_eq!(info.keys, vec!["lower(resource_id)", "during"]);
+        assert_eq!(info.old.get("lower(resource_id)").unwrap(), "room-1");
+        // there's no resource or user key to name the window with
+        assert!(ReservationConflict::from_str(s).is_err());
+
+        let s = "Key (resource_id, during)=(room-1, [\"2023-01-02 17:10:10+00\",\"2023-01-05 17:10:10+00\")) conflicts with existing key (resource_id, during)=(room-1, [\"2023-01-01 17:10:10+00\",\"2023-01-04 17:10:10+00\")).";
+        let conflict = ReservationConflict::from_str(s).unwrap();
+        assert_eq!(conflict.old.end, parse_time("2023-01-04 17:10:10+00").ok());
+    }
+
+    #[test]
+    fn ambiguous_or_broken_details_should_stay_unparsed() {
+        // an unquoted id containing ", " can't be told apart from the next value
+        let s = "Key (tenant_id, resource_id, timespan)=(acme, room, 1, [\"2023-01-02 17:10:10+00\",\"2023-01-05 17:10:10+00\")) conflicts with existing key (tenant_id, resource_id, timespan)=(acme, room, 1, [\"2023-01-01 17:10:10+00\",\"2023-01-04 17:10:10+00\")).";
+        for s in [
+            s,
+            "",
+            "Key (",
+            "Key (a)=(b) conflicts with existing key (a)=(c).",
+            "empty",
+        ] {
+            assert_eq!(
+                ReservationConflictInfo::from(s),
+                ReservationConflictInfo::Unparsed(s.to_string())
+            );
+        }
+    }
+
+    /// print a window the way postgres does
+    fn detail_values(
+        tenant: &str,
+        rid: &str,
+        start: &DateTime<Utc>,
+        end: &DateTime<Utc>,
+    ) -> String {
+        let fmt = "%Y-%m-%d %H:%M:%S%.f%:z";
+        format!(
+            "{}, {}, [\"{}\",\"{}\")",
+            tenant,
+            rid,
+            start.format(fmt),
+            end.format(fmt)
+        )
+    }
+
+    fn time() -> impl Strategy<Value = DateTime<Utc>> {
+        (0i64..4_102_444_800, 0u32..1_000_000)
+            .prop_map(|(secs, micros)| DateTime::from_timestamp(secs, micros * 1000).unwrap())
+    }
+
+    proptest! {
+        #[test]
+        fn any_input_should_not_panic(s in "\\PC*") {
+            let _ = ReservationConflictInfo::from(s.as_str());
+        }
+
+        #[test]
+        fn printed_conflicts_should_parse_back(
+            tenant in "[a-z0-9_-]{1,12}",
+            rid in "[a-zA-Z0-9_ .:-]{0,20}[a-zA-Z0-9]",
+            new_start in time(),
+            old_start in time(),
+            lengths in (1i64..1_000_000, 1i64..1_000_000),
+        ) {
+            let new_end = new_start + chrono::Duration::seconds(lengths.0);
+            let old_end = old_start + chrono::Duration::seconds(lengths.1);
+            let s = format!(
+                "Key (tenant_id, resource_id, timespan)=({}) conflicts with existing key (tenant_id, resource_id, timespan)=({}).",
+                detail_values(&tenant, &rid, &new_start, &new_end),
+                detail_values(&tenant, &rid, &old_start, &old_end),
+            );
+
+            let conflict = ReservationConflict::from_str(&s).unwrap();
+            prop_assert_eq!(&conflict.new.rid, &rid);
+            prop_assert_eq!(conflict.new.start, Some(new_start));
+            prop_assert_eq!(conflict.new.end, Some(new_end));
+            prop_assert_eq!(conflict.old.start, Some(old_start));
+            prop_assert_eq!(conflict.old.end, Some(old_end));
+        }
+    }
 }
@@ -0,0 +1,190 @@
+use prost::Message;
+use tonic::{codegen::Bytes, Code, Status};
+
+use super::{BatchConflict, ConflictKind, ReservationConflictInfo, ReservationWindow};
+use crate::{
+    convert_to_timestamp, ConflictDetail, ConflictDetails, ConflictReason, ConflictWindow,
+};
+
+impl ConflictDetails {
+    /// the conflicts attached to an ALREADY_EXISTS status, None for any other status
+    pub fn from_status(status: &Status) -> Option<Self> {
+        if status.code() != Code::AlreadyExists || status.details().is_empty() {
+            return None;
+        }
+        Self::decode(status.details()).ok()
+    }
+
+    pub(crate) fn from_batch(conflicts: &[BatchConflict]) -> Self {
+        Self {
+            conflicts: conflicts
+                .iter()
+                .map(|c| ConflictDetail::new(c.index, &c.info))
+                .collect(),
+        }
+    }
+
+    pub(crate) fn into_status(self, message: String) -> Status {
+        Status::with_details(
+            Code::AlreadyExists,
+            message,
+            Bytes::from(self.encode_to_vec()),
+        )
+    }
+}
+
+impl ConflictDetail {
+    pub fn new(index: usize, info: &ReservationConflictInfo) -> Self {
+        match info {
+            ReservationConflictInfo::Parsed(conflict) => Self {
+                reason: ConflictReason::from(conflict.kind) as i32,
+                existing: Some((&conflict.old).into()),
+                requested: Some(ConflictWindow {
+                    reservation_id: 0,
+                    ..(&conflict.new).into()
+                }),
+                index: index as _,
+                detail: String::new(),
+                overlapping: conflict.overlapping.iter().map(Into::into).collect(),
+                suggestions: conflict.suggestions.iter().map(Into::into).collect(),
+            },
+            ReservationConflictInfo::Unparsed(detail) => Self {
+                reason: ConflictReason::Unknown as i32,
+                index: index as _,
+                detail: detail.clone(),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl From<ConflictKind> for ConflictReason {
+    fn from(kind: ConflictKind) -> Self {
+        match kind {
+            ConflictKind::Overlap => ConflictReason::Overlap,
+            ConflictKind::Buffer => ConflictReason::Buffer,
+            ConflictKind::User => ConflictReason::User,
+        }
+    }
+}
+
+impl From<&ReservationWindow> for ConflictWindow {
+    fn from(window: &ReservationWindow) -> Self {
+        Self {
+            reservation_id: window.id,
+            resource_id: window.rid.clone(),
+            user_id: window.uid.clone(),
+            start: window.start.as_ref().map(convert_to_timestamp),
+            end: window.end.as_ref().map(convert_to_timestamp),
+            time_zone: window.time_zone.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BatchFailure, Error};
+
+    const S: &str = "Key (resource_id, timespan)=(room-1, [\"2023-01-02 17:10:10+00\",\"2023-01-05 17:10:10+00\")) conflicts with existing key (resource_id, timespan)=(room-1, [\"2023-01-01 17:10:10+00\",\"2023-01-04 17:10:10+00\")).";
+
+    #[test]
+    fn conflict_status_should_carry_details() {
+        let mut info: ReservationConflictInfo = S.parse().unwrap();
+        if let ReservationConflictInfo::Parsed(conflict) = &mut info {
+            conflict.old.id = 42;
+        }
+        let status = Status::from(Error::ConflictReservation(info));
+        let details = ConflictDetails::from_status(&status).unwrap();
+
+        assert_eq!(details.conflicts.len(), 1);
+        let conflict = &details.conflicts[0];
+        assert_eq!(conflict.reason, ConflictReason::Overlap as i32);
+        let existing = conflict.existing.as_ref().unwrap();
+        assert_eq!(existing.reservation_id, 42);
+        assert_eq!(existing.resource_id, "room-1");
+        assert_eq!(existing.start.as_ref().unwrap().seconds, 1672593010);
+        let requested = conflict.requested.as_ref().unwrap();
+        assert_eq!(requested.resource_id, "room-1");
+        assert_eq!(requested.end.as_ref().unwrap().seconds, 1672938610);
+    }
+
+    #[test]
+    fn batch_status_should_carry_every_conflict() {
+        let conflicts = vec![
+            BatchConflict {
+                index: 1,
+                info: S.parse().unwrap(),
+            },
+            BatchConflict {
+                index: 3,
+                info: ReservationConflictInfo::Unparsed("boom".to_string()),
+            },
+        ];
+        let status = Status::from(Error::ConflictBatch(conflicts));
+        let details = ConflictDetails::from_status(&status).unwrap();
+
+        let indexes: Vec<_> = details.conflicts.iter().map(|c| c.index).collect();
+        assert_eq!(indexes, vec![1, 3]);
+        assert_eq!(details.conflicts[1].reason, ConflictReason::Unknown as i32);
+        assert_eq!(details.conflicts[1].detail, "boom");
+        assert!(details.conflicts[1].existing.is_none());
+    }
+
+    #[test]
+    fn batch_failure_status_should_name_every_item() {
+        let failures = vec![
+            BatchFailure {
+                index: 0,
+                error: Error::InvalidUserId("".to_string()),
+            },
+            BatchFailure {
+                index: 2,
+                error: Error::OutsideOpeningHours,
+            },
+        ];
+        let status = Status::from(Error::BatchFailed(failures));
+
+        assert_eq!(status.code(), Code::InvalidArgument);
+        assert!(status.message().contains("item 0: Invalid user id"));
+        assert!(status.message().contains("item 2: reservation window"));
+        assert!(ConflictDetails::from_status(&status).is_none());
+
+        // a conflict among them keeps its details and index
+        let failures = vec![
+            BatchFailure {
+                index: 0,
+                error: Error::InvalidUserId("".to_string()),
+            },
+            BatchFailure {
+                index: 2,
+                error: Error::ConflictReservation(S.parse().unwrap()),
+            },
+        ];
+        let status = Status::from(Error::BatchFailed(failures));
+        assert!(status.message().contains("item 0: Invalid user id"));
+        let details = ConflictDetails::from_status(&status).unwrap();
+        assert_eq!(details.conflicts.len(), 1);
+        let conflict = &details.conflicts[0];
+        assert_eq!(conflict.index, 2);
+        assert_eq!(conflict.reason, ConflictReason::Overlap as i32);
+        assert_eq!(conflict.existing.as_ref().unwrap().resource_id, "room-1");
+        assert_eq!(
+            conflict
+                .requested
+                .as_ref()
+                .unwrap()
+                .end
+                .as_ref()
+                .unwrap()
+                .seconds,
+            1672938610
+        );
+    }
+
+    #[test]
+    fn other_statuses_should_have_no_conflicts() {
+        assert!(ConflictDetails::from_status(&Status::from(Error::NotFound)).is_none());
+        assert!(ConflictDetails::from_status(&Status::already_exists("x")).is_none());
+    }
+}
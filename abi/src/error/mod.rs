@@ -1,10 +1,17 @@
 mod conflict;
+mod details;
+mod policy;
+mod quota;
 
 use sqlx::postgres::PgDatabaseError;
 
 pub use conflict::*;
+pub use policy::PolicyRule;
+pub use quota::{QuotaKind, QuotaUsage};
 use tonic::Status;
 
+use crate::{ConflictDetail, ConflictDetails};
+
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error("database error")]
@@ -28,6 +35,9 @@ pub enum Error {
     #[error("Invalid resource id: {0}")]
     InvalidResourceId(String),
 
+    #[error("Invalid waitlist id: {0}")]
+    InvalidWaitlistId(i64),
+
     #[error("missing argument: {0}")]
     MissingArgument(String),
 
@@ -40,16 +50,89 @@ pub enum Error {
     #[error("Invalid status: {0}")]
     InvalidStatus(i32),
 
+    #[error("Invalid update mask path: {0}")]
+    InvalidUpdateMask(String),
+
+    #[error("Invalid duration: {0}")]
+    InvalidDuration(String),
+
+    #[error("Invalid time zone: {0}")]
+    InvalidTimeZone(String),
+
+    #[error("Invalid recurrence interval: {0} days")]
+    InvalidRecurrence(u32),
+
+    #[error("Invalid check in and check out dates: {0}")]
+    InvalidDateRange(String),
+
+    #[error("Invalid opening hours: {0}")]
+    InvalidOpeningHours(String),
+
+    #[error("Invalid blackout date: {0}")]
+    InvalidBlackoutDate(String),
+
+    #[error("Invalid tenant id: {0}")]
+    InvalidTenantId(String),
+
+    #[error("Invalid metadata: {0}")]
+    InvalidMetadata(String),
+
+    #[error("Invalid attendee: {0}")]
+    InvalidAttendee(String),
+
+    #[error("Invalid idempotency key: {0}")]
+    InvalidIdempotencyKey(String),
+
+    #[error("idempotency key {0} was already used for a different request")]
+    IdempotencyKeyReused(String),
+
+    #[error("request with idempotency key {0} is still in progress")]
+    IdempotencyInProgress(String),
+
+    #[error("reservation window is outside the opening hours of the resource")]
+    OutsideOpeningHours,
+
+    #[error("reservation violates the booking policy: {0}")]
+    PolicyViolation(PolicyRule),
+
+    #[error("quota exceeded: {0}")]
+    QuotaExceeded(QuotaUsage),
+
+    #[error("reservation {0} must be approved by an approver of the resource")]
+    ApprovalRequired(i64),
+
+    #[error("{0} is not an approver of the resource")]
+    NotApprover(String),
+
+    #[error("{0} is not the organizer of the reservation")]
+    NotOrganizer(String),
+
+    #[error("reservation was changed meanwhile, expected version {0} but it is {1}")]
+    VersionMismatch(i64, i64),
+
     #[error("Not found the reservation by given condition")]
     NotFound,
 
     #[error("Conflict reservation")]
     ConflictReservation(ReservationConflictInfo),
 
+    #[error("Conflict reservation in batch")]
+    ConflictBatch(Vec<BatchConflict>),
+
+    #[error("reservation failed in batch")]
+    BatchFailed(Vec<BatchFailure>),
+
     #[error("unknown data store error")]
     Unknown,
 }
 
+/// an item of a batch reservation which can't be reserved, index is the position in the request
+#[derive(Debug, PartialEq)]
+pub struct BatchFailure {
+    pub index: usize,
+    pub error: Error,
+}
+
 impl From<sqlx::Error> for Error {
     fn from(err: sqlx::Error) -> Self {
         match err {
@@ -57,7 +140,17 @@ impl From<sqlx::Error> for Error {
                 let e: &PgDatabaseError = err.downcast_ref();
                 match (e.code(), e.schema(), e.table()) {
                     ("23P01", Some("rsvp"), Some("reservations")) => {
-                        Error::ConflictReservation(e.detail().unwrap().parse().unwrap())
+                        // an unparsed conflict is looked up by the caller
+                        let mut info =
+                            ReservationConflictInfo::from(e.detail().unwrap_or_default());
+                        match e.constraint() {
+                            Some("reservation_buffer_conflict") => {
+                                info.set_kind(ConflictKind::Buffer)
+                            }
+                            Some("reservation_user_conflict") => info.set_kind(ConflictKind::User),
+                            _ => {}
+                        }
+                        Error::ConflictReservation(info)
                     }
                     _ => Error::DbError(sqlx::Error::Database(err)),
                 }
@@ -76,7 +169,10 @@ impl PartialEq for Error {
             (Self::InvalidUserId(a), Self::InvalidUserId(b)) => a == b,
             (Self::InvalidReservationId(a), Self::InvalidReservationId(b)) => a == b,
             (Self::InvalidResourceId(a), Self::InvalidResourceId(b)) => a == b,
+            (Self::InvalidWaitlistId(a), Self::InvalidWaitlistId(b)) => a == b,
             (Self::ConflictReservation(a), Self::ConflictReservation(b)) => a == b,
+            (Self::ConflictBatch(a), Self::ConflictBatch(b)) => a == b,
+            (Self::BatchFailed(a), Self::BatchFailed(b)) => a == b,
             (Self::NotFound, Self::NotFound) => true,
             (Self::Unknown, Self::Unknown) => true,
             (Self::ConfigReadError, Self::ConfigReadError) => true,
@@ -85,6 +181,26 @@ impl PartialEq for Error {
             (Self::InvalidPageSize(a), Self::InvalidPageSize(b)) => a == b,
             (Self::InvalidCursor(a), Self::InvalidCursor(b)) => a == b,
             (Self::InvalidStatus(a), Self::InvalidStatus(b)) => a == b,
+            (Self::InvalidUpdateMask(a), Self::InvalidUpdateMask(b)) => a == b,
+            (Self::InvalidDuration(a), Self::InvalidDuration(b)) => a == b,
+            (Self::InvalidTimeZone(a), Self::InvalidTimeZone(b)) => a == b,
+            (Self::InvalidRecurrence(a), Self::InvalidRecurrence(b)) => a == b,
+            (Self::InvalidDateRange(a), Self::InvalidDateRange(b)) => a == b,
+            (Self::InvalidOpeningHours(a), Self::InvalidOpeningHours(b)) => a == b,
+            (Self::InvalidBlackoutDate(a), Self::InvalidBlackoutDate(b)) => a == b,
+            (Self::InvalidTenantId(a), Self::InvalidTenantId(b)) => a == b,
+            (Self::InvalidMetadata(a), Self::InvalidMetadata(b)) => a == b,
+            (Self::InvalidAttendee(a), Self::InvalidAttendee(b)) => a == b,
+            (Self::InvalidIdempotencyKey(a), Self::InvalidIdempotencyKey(b)) => a == b,
+            (Self::IdempotencyKeyReused(a), Self::IdempotencyKeyReused(b)) => a == b,
+            (Self::IdempotencyInProgress(a), Self::IdempotencyInProgress(b)) => a == b,
+            (Self::OutsideOpeningHours, Self::OutsideOpeningHours) => true,
+            (Self::PolicyViolation(a), Self::PolicyViolation(b)) => a == b,
+            (Self::QuotaExceeded(a), Self::QuotaExceeded(b)) => a == b,
+            (Self::ApprovalRequired(a), Self::ApprovalRequired(b)) => a == b,
+            (Self::NotApprover(a), Self::NotApprover(b)) => a == b,
+            (Self::NotOrganizer(a), Self::NotOrganizer(b)) => a == b,
+            (Self::VersionMismatch(a1, b1), Self::VersionMismatch(a2, b2)) => a1 == a2 && b1 == b2,
             _ => false,
         }
     }
@@ -100,13 +216,74 @@ impl From<Error> for tonic::Status {
             | Error::InvalidUserId(_)
             | Error::InvalidReservationId(_)
             | Error::InvalidResourceId(_)
+            | Error::InvalidWaitlistId(_)
             | Error::MissingArgument(_)
             | Error::InvalidCursor(_)
             | Error::InvalidPageSize(_)
-            | Error::InvalidStatus(_) => Status::invalid_argument(err.to_string()),
+            | Error::InvalidStatus(_)
+            | Error::InvalidUpdateMask(_)
+            | Error::InvalidDuration(_)
+            | Error::InvalidTimeZone(_)
+            | Error::InvalidRecurrence(_)
+            | Error::InvalidDateRange(_)
+            | Error::InvalidOpeningHours(_)
+            | Error::InvalidBlackoutDate(_)
+            | Error::InvalidTenantId(_)
+            | Error::InvalidMetadata(_)
+            | Error::InvalidAttendee(_)
+            | Error::InvalidIdempotencyKey(_)
+            | Error::IdempotencyKeyReused(_)
+            | Error::OutsideOpeningHours => Status::invalid_argument(err.to_string()),
+            Error::PolicyViolation(_) | Error::ApprovalRequired(_) => {
+                Status::failed_precondition(err.to_string())
+            }
+            Error::NotApprover(_) | Error::NotOrganizer(_) => {
+                Status::permission_denied(err.to_string())
+            }
+            Error::QuotaExceeded(_) => Status::resource_exhausted(err.to_string()),
+            Error::VersionMismatch(..) | Error::IdempotencyInProgress(_) => {
+                Status::aborted(err.to_string())
+            }
             Error::NotFound => Status::not_found("not found the reservation by given condition"),
-            Error::ConflictReservation(info) => {
-                Status::already_exists(format!("Conflict reservation: {:?}", info))
+            Error::ConflictReservation(info) => ConflictDetails {
+                conflicts: vec![ConflictDetail::new(0, &info)],
+            }
+            .into_status(format!("Conflict reservation: {:?}", info)),
+            Error::ConflictBatch(conflicts) => {
+                let items: Vec<String> = conflicts
+                    .iter()
+                    .map(|c| format!("item {}: {:?}", c.index, c.info))
+                    .collect();
+                ConflictDetails::from_batch(&conflicts).into_status(format!(
+                    "Conflict reservation in batch: {}",
+                    items.join("; ")
+                ))
+            }
+            Error::BatchFailed(failures) => {
+                let items: Vec<String> = failures
+                    .iter()
+                    .map(|f| format!("item {}: {}", f.index, f.error))
+                    .collect();
+                let message = format!("reservation failed in batch: {}", items.join("; "));
+                // conflicting items keep their details, the other items are in the message
+                let conflicts: Vec<ConflictDetail> = failures
+                    .iter()
+                    .filter_map(|f| match &f.error {
+                        Error::ConflictReservation(info) => {
+                            Some(ConflictDetail::new(f.index, info))
+                        }
+                        _ => None,
+                    })
+                    .collect();
+                if !conflicts.is_empty() {
+                    return ConflictDetails { conflicts }.into_status(message);
+                }
+                // failures are in request order, the first item decides the code
+                let code = match failures.into_iter().next() {
+                    Some(f) => Status::from(f.error).code(),
+                    None => tonic::Code::Internal,
+                };
+                Status::new(code, message)
             }
             Error::Unknown => Status::internal("unknown error"),
         }
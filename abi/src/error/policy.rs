@@ -0,0 +1,24 @@
+use std::fmt;
+
+/// the booking policy rule a reservation failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolicyRule {
+    MinDuration,
+    MaxDuration,
+    MinLeadTime,
+    MaxAdvance,
+    SlotAlignment,
+}
+
+impl fmt::Display for PolicyRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            PolicyRule::MinDuration => "min_duration",
+            PolicyRule::MaxDuration => "max_duration",
+            PolicyRule::MinLeadTime => "min_lead_time",
+            PolicyRule::MaxAdvance => "max_advance",
+            PolicyRule::SlotAlignment => "slot_alignment",
+        };
+        f.write_str(name)
+    }
+}
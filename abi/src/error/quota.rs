@@ -0,0 +1,35 @@
+use std::fmt;
+
+/// the quota a user ran out of
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotaKind {
+    MaxActive,
+    MaxPending,
+    MaxWeeklyDuration,
+}
+
+/// current usage and limit of a quota, durations are in seconds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuotaUsage {
+    pub kind: QuotaKind,
+    pub usage: i64,
+    pub limit: i64,
+}
+
+impl fmt::Display for QuotaUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            QuotaKind::MaxActive => {
+                write!(f, "max_active, usage {}, limit {}", self.usage, self.limit)
+            }
+            QuotaKind::MaxPending => {
+                write!(f, "max_pending, usage {}, limit {}", self.usage, self.limit)
+            }
+            QuotaKind::MaxWeeklyDuration => write!(
+                f,
+                "max_weekly_duration, usage {}s, limit {}s",
+                self.usage, self.limit
+            ),
+        }
+    }
+}
@@ -6,9 +6,13 @@ mod types;
 mod utils;
 
 pub use config::*;
-pub use error::{Error, ReservationConflict, ReservationConflictInfo, ReservationWindow};
+pub use error::{
+    BatchConflict, BatchFailure, ConflictKind, Error, PolicyRule, QuotaKind, QuotaUsage,
+    ReservationConflict, ReservationConflictInfo, ReservationWindow,
+};
 pub use pager::*;
 pub use pb::*;
+pub use types::{get_timespan, validate_range};
 pub use utils::*;
 
 /// 为了方便, 将一些类型定义在这里
@@ -16,6 +20,10 @@ pub use utils::*;
 pub type ReservationId = i64;
 pub type ResourceId = String;
 pub type UserId = String;
+pub type TenantId = String;
+
+/// tenant of requests which don't name one
+pub const DEFAULT_TENANT: &str = "default";
 
 pub trait Validator {
     fn validate(&self) -> Result<(), Error>;
@@ -31,8 +39,10 @@ pub trait Normalizer: Validator {
     fn do_normalize(&mut self);
 }
 
+/// the statement takes the tenant id as $1, the metadata to match, as jsonb, as $2,
+/// the user id as $3 and the resource id as $4
 pub trait ToSql {
-    fn to_sql(&self) -> String;
+    fn to_sql(&self) -> Result<String, Error>;
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
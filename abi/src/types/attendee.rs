@@ -0,0 +1,218 @@
+use std::collections::HashSet;
+
+use crate::{Attendee, AttendeeRole, Error, Reservation, RsvpState};
+
+impl Attendee {
+    pub fn new(user_id: impl Into<String>, role: AttendeeRole) -> Self {
+        Self {
+            user_id: user_id.into(),
+            role: role as i32,
+            rsvp_state: RsvpState::NeedsAction as i32,
+        }
+    }
+
+    pub fn with_state(mut self, state: RsvpState) -> Self {
+        self.rsvp_state = state as i32;
+        self
+    }
+}
+
+impl Reservation {
+    /// apply an attendee change made by caller, the organizer can change everyone,
+    /// anybody else can only answer their own invitation
+    pub fn update_attendees(
+        &mut self,
+        caller: &str,
+        add: Vec<Attendee>,
+        remove: Vec<String>,
+    ) -> Result<(), Error> {
+        if caller != self.user_id {
+            let answer = match (add.as_slice(), remove.is_empty()) {
+                ([a], true) if a.user_id == caller => a,
+                _ => return Err(Error::NotOrganizer(caller.to_string())),
+            };
+            match self.attendees.iter_mut().find(|a| a.user_id == caller) {
+                Some(current) if current.role == answer.role => {
+                    current.rsvp_state = answer.rsvp_state;
+                }
+                _ => return Err(Error::NotOrganizer(caller.to_string())),
+            }
+            return validate_attendees(&self.user_id, &self.attendees);
+        }
+
+        self.attendees.retain(|a| !remove.contains(&a.user_id));
+        for attendee in add {
+            match self
+                .attendees
+                .iter_mut()
+                .find(|a| a.user_id == attendee.user_id)
+            {
+                Some(current) => *current = attendee,
+                None => self.attendees.push(attendee),
+            }
+        }
+
+        validate_attendees(&self.user_id, &self.attendees)
+    }
+
+    /// hand the organizer role of a meeting to the current owner, after user_id changed from
+    /// previous. the previous owner stays a required attendee
+    pub(crate) fn transfer_organizer(&mut self, previous: &str) {
+        if previous == self.user_id
+            || !self
+                .attendees
+                .iter()
+                .any(|a| a.role == AttendeeRole::Organizer as i32)
+        {
+            return;
+        }
+
+        for a in self.attendees.iter_mut() {
+            if a.user_id == previous {
+                a.role = AttendeeRole::Required as i32;
+            }
+        }
+        match self
+            .attendees
+            .iter_mut()
+            .find(|a| a.user_id == self.user_id)
+        {
+            Some(owner) => owner.role = AttendeeRole::Organizer as i32,
+            None => self
+                .attendees
+                .insert(0, Attendee::new(&self.user_id, AttendeeRole::Organizer)),
+        }
+    }
+}
+
+/// every attendee appears once with a known role and state, only the owner can be the organizer
+pub fn validate_attendees(owner: &str, attendees: &[Attendee]) -> Result<(), Error> {
+    let mut seen = HashSet::new();
+    for a in attendees {
+        if a.user_id.is_empty() || !seen.insert(a.user_id.as_str()) {
+            return Err(Error::InvalidAttendee(a.user_id.clone()));
+        }
+
+        let role = AttendeeRole::from_i32(a.role).unwrap_or(AttendeeRole::Unknown);
+        let state = RsvpState::from_i32(a.rsvp_state).unwrap_or(RsvpState::Unknown);
+        if role == AttendeeRole::Unknown
+            || state == RsvpState::Unknown
+            || (role == AttendeeRole::Organizer) != (a.user_id == owner)
+        {
+            return Err(Error::InvalidAttendee(a.user_id.clone()));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meeting() -> Reservation {
+        Reservation {
+            user_id: "alice".to_string(),
+            attendees: vec![
+                Attendee::new("alice", AttendeeRole::Organizer).with_state(RsvpState::Accepted),
+                Attendee::new("bob", AttendeeRole::Required),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn organizer_should_change_attendees() {
+        let mut rsvp = meeting();
+        rsvp.update_attendees(
+            "alice",
+            vec![
+                Attendee::new("carol", AttendeeRole::Optional),
+                Attendee::new("bob", AttendeeRole::Optional),
+            ],
+            vec![],
+        )
+        .unwrap();
+        assert_eq!(rsvp.attendees.len(), 3);
+        assert_eq!(rsvp.attendees[1].role, AttendeeRole::Optional as i32);
+
+        rsvp.update_attendees("alice", vec![], vec!["bob".to_string()])
+            .unwrap();
+        let ids: Vec<_> = rsvp.attendees.iter().map(|a| a.user_id.as_str()).collect();
+        assert_eq!(ids, vec!["alice", "carol"]);
+    }
+
+    #[test]
+    fn attendee_should_only_answer_for_themselves() {
+        let mut rsvp = meeting();
+        let answer = Attendee::new("bob", AttendeeRole::Required).with_state(RsvpState::Declined);
+        rsvp.update_attendees("bob", vec![answer], vec![]).unwrap();
+        assert_eq!(rsvp.attendees[1].rsvp_state, RsvpState::Declined as i32);
+
+        // promoting themselves or touching others is the organizer's job
+        let err = rsvp
+            .update_attendees(
+                "bob",
+                vec![Attendee::new("bob", AttendeeRole::Optional)],
+                vec![],
+            )
+            .unwrap_err();
+        assert_eq!(err, Error::NotOrganizer("bob".to_string()));
+        let err = rsvp
+            .update_attendees("bob", vec![], vec!["alice".to_string()])
+            .unwrap_err();
+        assert_eq!(err, Error::NotOrganizer("bob".to_string()));
+        assert_eq!(
+            rsvp.update_attendees(
+                "carol",
+                vec![Attendee::new("carol", AttendeeRole::Required)],
+                vec![]
+            )
+            .unwrap_err(),
+            Error::NotOrganizer("carol".to_string())
+        );
+    }
+
+    #[test]
+    fn transfer_organizer_should_follow_the_owner() {
+        let mut rsvp = meeting();
+        rsvp.user_id = "bob".to_string();
+        rsvp.transfer_organizer("alice");
+        assert_eq!(rsvp.attendees[0].role, AttendeeRole::Required as i32);
+        assert_eq!(rsvp.attendees[0].rsvp_state, RsvpState::Accepted as i32);
+        assert_eq!(rsvp.attendees[1].role, AttendeeRole::Organizer as i32);
+        validate_attendees(&rsvp.user_id, &rsvp.attendees).unwrap();
+
+        // a new owner who wasn't invited is added as the organizer
+        rsvp.user_id = "carol".to_string();
+        rsvp.transfer_organizer("bob");
+        let ids: Vec<_> = rsvp.attendees.iter().map(|a| a.user_id.as_str()).collect();
+        assert_eq!(ids, vec!["carol", "alice", "bob"]);
+        validate_attendees(&rsvp.user_id, &rsvp.attendees).unwrap();
+    }
+
+    #[test]
+    fn validate_attendees_should_reject_bad_lists() {
+        let bob = Attendee::new("bob", AttendeeRole::Required);
+        assert!(validate_attendees("alice", std::slice::from_ref(&bob)).is_ok());
+        assert_eq!(
+            validate_attendees("alice", &[bob.clone(), bob.clone()]).unwrap_err(),
+            Error::InvalidAttendee("bob".to_string())
+        );
+        assert_eq!(
+            validate_attendees("alice", &[Attendee::new("bob", AttendeeRole::Organizer)])
+                .unwrap_err(),
+            Error::InvalidAttendee("bob".to_string())
+        );
+        assert_eq!(
+            validate_attendees("alice", &[Attendee::new("alice", AttendeeRole::Required)])
+                .unwrap_err(),
+            Error::InvalidAttendee("alice".to_string())
+        );
+        assert_eq!(
+            validate_attendees("alice", &[Attendee::new("bob", AttendeeRole::Unknown)])
+                .unwrap_err(),
+            Error::InvalidAttendee("bob".to_string())
+        );
+    }
+}
@@ -1,41 +1,80 @@
+mod attendee;
+mod policy;
 mod request;
 mod reservation;
 mod reservation_filter;
 mod reservation_query;
 mod reservation_status;
+mod resource;
+mod waitlist;
 
 use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use prost_types::Timestamp;
 use sqlx::postgres::types::PgRange;
-use std::ops::Bound;
-
-use crate::{convert_to_utc_time, Error};
-
-pub fn get_timespan(start: Option<&Timestamp>, end: Option<&Timestamp>) -> PgRange<DateTime<Utc>> {
-    let start = convert_to_utc_time(start.as_ref().unwrap());
-    let end = convert_to_utc_time(end.as_ref().unwrap());
-
-    PgRange {
-        start: Bound::Included(start),
-        end: Bound::Excluded(end),
-    }
+use std::{collections::HashMap, ops::Bound};
+
+use crate::{convert_to_utc_time, truncate_to_micros, Error};
+
+/// the range stored for [start, end), truncated to the microseconds postgres keeps
+pub fn get_timespan(
+    start: Option<&Timestamp>,
+    end: Option<&Timestamp>,
+) -> Result<PgRange<DateTime<Utc>>, Error> {
+    let (start, end) = start.zip(end).ok_or(Error::InvalidTimespan)?;
+    Ok(PgRange {
+        start: Bound::Included(convert_to_utc_time(&truncate_to_micros(start))?),
+        end: Bound::Excluded(convert_to_utc_time(&truncate_to_micros(end))?),
+    })
 }
 
+/// start must be before end once both are stored, a range shorter than a microsecond is empty
 pub fn validate_range(start: Option<&Timestamp>, end: Option<&Timestamp>) -> Result<(), Error> {
-    if start.is_none() || end.is_none() {
-        return Err(Error::InvalidTimespan);
+    let timespan = get_timespan(start, end)?;
+    match (timespan.start, timespan.end) {
+        (Bound::Included(start), Bound::Excluded(end)) if start < end => Ok(()),
+        _ => Err(Error::InvalidTimespan),
     }
+}
 
-    let start = start.unwrap();
-    let end = end.unwrap();
+/// an IANA time zone name, empty means UTC
+pub fn parse_time_zone(time_zone: &str) -> Result<Tz, Error> {
+    if time_zone.is_empty() {
+        return Ok(Tz::UTC);
+    }
+    time_zone
+        .parse()
+        .map_err(|_| Error::InvalidTimeZone(time_zone.to_string()))
+}
 
-    if start.seconds >= end.seconds {
-        return Err(Error::InvalidTimespan);
+/// metadata keys must be non empty, values may be anything
+pub fn validate_metadata(metadata: &HashMap<String, String>) -> Result<(), Error> {
+    if metadata.keys().any(|k| k.trim().is_empty()) {
+        return Err(Error::InvalidMetadata("empty key".to_string()));
     }
 
     Ok(())
 }
 
+pub(crate) struct NaiveRange<T> {
+    pub start: Option<T>,
+    pub end: Option<T>,
+}
+
+impl<T> From<PgRange<T>> for NaiveRange<T> {
+    fn from(range: PgRange<T>) -> Self {
+        let f = |b: Bound<T>| match b {
+            Bound::Included(v) => Some(v),
+            Bound::Excluded(v) => Some(v),
+            Bound::Unbounded => None,
+        };
+        let start = f(range.start);
+        let end = f(range.end);
+
+        Self { start, end }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -79,15 +118,54 @@ mod tests {
             nanos: 0,
         });
 
-        let timespan = get_timespan(start.as_ref(), end.as_ref());
+        let timespan = get_timespan(start.as_ref(), end.as_ref()).unwrap();
 
         assert_eq!(
             timespan.start,
-            Bound::Included(convert_to_utc_time(start.as_ref().unwrap()))
+            Bound::Included(convert_to_utc_time(start.as_ref().unwrap()).unwrap())
+        );
+        assert_eq!(
+            timespan.end,
+            Bound::Excluded(convert_to_utc_time(end.as_ref().unwrap()).unwrap())
+        );
+    }
+
+    #[test]
+    fn validate_range_should_compare_nanos() {
+        let ts = |seconds, nanos| Some(Timestamp { seconds, nanos });
+        assert!(validate_range(ts(0, 0).as_ref(), ts(0, 5_000).as_ref()).is_ok());
+        assert!(validate_range(ts(0, 5_000).as_ref(), ts(0, 0).as_ref()).is_err());
+        assert!(validate_range(ts(-1, 999_999_000).as_ref(), ts(0, 0).as_ref()).is_ok());
+
+        // within the same microsecond the stored range would be empty
+        assert!(validate_range(ts(0, 100).as_ref(), ts(0, 900).as_ref()).is_err());
+        assert!(validate_range(ts(0, 0).as_ref(), None).is_err());
+        assert!(validate_range(ts(0, 0).as_ref(), ts(i64::MAX, 0).as_ref()).is_err());
+    }
+
+    #[test]
+    fn get_timespan_should_truncate_to_micros() {
+        let ts = |seconds, nanos| Some(Timestamp { seconds, nanos });
+        let timespan = get_timespan(ts(-1, 1_999).as_ref(), ts(1, 999_999_999).as_ref()).unwrap();
+        assert_eq!(
+            timespan.start,
+            Bound::Included(
+                convert_to_utc_time(&Timestamp {
+                    seconds: -1,
+                    nanos: 1_000
+                })
+                .unwrap()
+            )
         );
         assert_eq!(
             timespan.end,
-            Bound::Excluded(convert_to_utc_time(end.as_ref().unwrap()))
+            Bound::Excluded(
+                convert_to_utc_time(&Timestamp {
+                    seconds: 1,
+                    nanos: 999_999_000
+                })
+                .unwrap()
+            )
         );
     }
 }
@@ -0,0 +1,226 @@
+use prost_types::Duration;
+use sqlx::{
+    postgres::{types::PgInterval, PgRow},
+    FromRow, Row,
+};
+
+use crate::{
+    convert_to_duration, convert_to_interval, BookingPolicy, Error, Quota, ResourceType, Validator,
+};
+
+impl BookingPolicy {
+    /// rules not set here are taken from fallback
+    pub fn or(self, fallback: BookingPolicy) -> BookingPolicy {
+        BookingPolicy {
+            min_duration: self.min_duration.or(fallback.min_duration),
+            max_duration: self.max_duration.or(fallback.max_duration),
+            min_lead_time: self.min_lead_time.or(fallback.min_lead_time),
+            max_advance: self.max_advance.or(fallback.max_advance),
+            slot_alignment: self.slot_alignment.or(fallback.slot_alignment),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self == &BookingPolicy::default()
+    }
+
+    /// rules as intervals, in the order of the table columns
+    pub fn get_intervals(&self) -> [Option<PgInterval>; 5] {
+        self.rules().map(|(_, d)| d.map(convert_to_interval))
+    }
+
+    fn rules(&self) -> [(&'static str, Option<&Duration>); 5] {
+        [
+            ("min_duration", self.min_duration.as_ref()),
+            ("max_duration", self.max_duration.as_ref()),
+            ("min_lead_time", self.min_lead_time.as_ref()),
+            ("max_advance", self.max_advance.as_ref()),
+            ("slot_alignment", self.slot_alignment.as_ref()),
+        ]
+    }
+}
+
+impl Validator for BookingPolicy {
+    fn validate(&self) -> Result<(), Error> {
+        for (name, d) in self.rules() {
+            if let Some(d) = d {
+                if d.seconds < 0 || d.nanos < 0 {
+                    return Err(Error::InvalidDuration(name.to_string()));
+                }
+            }
+        }
+
+        if let Some(d) = &self.slot_alignment {
+            if d.seconds == 0 && d.nanos == 0 {
+                return Err(Error::InvalidDuration("slot_alignment".to_string()));
+            }
+        }
+
+        if let (Some(min), Some(max)) = (&self.min_duration, &self.max_duration) {
+            if (min.seconds, min.nanos) > (max.seconds, max.nanos) {
+                return Err(Error::InvalidDuration("max_duration".to_string()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromRow<'_, PgRow> for BookingPolicy {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        let get = |name: &str| -> Result<Option<Duration>, sqlx::Error> {
+            let interval: Option<PgInterval> = row.try_get(name)?;
+            Ok(interval.as_ref().map(convert_to_duration))
+        };
+
+        Ok(Self {
+            min_duration: get("min_duration")?,
+            max_duration: get("max_duration")?,
+            min_lead_time: get("min_lead_time")?,
+            max_advance: get("max_advance")?,
+            slot_alignment: get("slot_alignment")?,
+        })
+    }
+}
+
+impl Quota {
+    pub fn get_max_weekly_duration(&self) -> Option<PgInterval> {
+        self.max_weekly_duration.as_ref().map(convert_to_interval)
+    }
+}
+
+impl Validator for Quota {
+    fn validate(&self) -> Result<(), Error> {
+        if let Some(d) = &self.max_weekly_duration {
+            if d.seconds < 0 || d.nanos < 0 {
+                return Err(Error::InvalidDuration("max_weekly_duration".to_string()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromRow<'_, PgRow> for Quota {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        let max_active: i32 = row.try_get("max_active")?;
+        let max_pending: i32 = row.try_get("max_pending")?;
+        let max_weekly_duration: Option<PgInterval> = row.try_get("max_weekly_duration")?;
+
+        Ok(Self {
+            max_active: max_active as _,
+            max_pending: max_pending as _,
+            max_weekly_duration: max_weekly_duration.as_ref().map(convert_to_duration),
+        })
+    }
+}
+
+impl ResourceType {
+    pub fn new(id: impl Into<String>, policy: BookingPolicy) -> Self {
+        Self {
+            id: id.into(),
+            policy: Some(policy),
+            quota: Some(Quota::default()),
+            user_exclusive: false,
+        }
+    }
+
+    pub fn with_quota(mut self, quota: Quota) -> Self {
+        self.quota = Some(quota);
+        self
+    }
+
+    pub fn with_user_exclusive(mut self) -> Self {
+        self.user_exclusive = true;
+        self
+    }
+}
+
+impl Validator for ResourceType {
+    fn validate(&self) -> Result<(), Error> {
+        if self.id.is_empty() {
+            return Err(Error::MissingArgument("resource_type.id".to_string()));
+        }
+        if let Some(policy) = &self.policy {
+            policy.validate()?;
+        }
+        if let Some(quota) = &self.quota {
+            quota.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromRow<'_, PgRow> for ResourceType {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.get("id"),
+            policy: Some(BookingPolicy::from_row(row)?),
+            quota: Some(Quota::from_row(row)?),
+            user_exclusive: row.get("user_exclusive"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn minutes(m: i64) -> Option<Duration> {
+        Some(Duration {
+            seconds: m * 60,
+            nanos: 0,
+        })
+    }
+
+    #[test]
+    fn policy_should_fall_back_per_rule() {
+        let resource = BookingPolicy {
+            max_duration: minutes(60),
+            ..Default::default()
+        };
+        let resource_type = BookingPolicy {
+            min_duration: minutes(30),
+            max_duration: minutes(120),
+            ..Default::default()
+        };
+
+        let policy = resource.or(resource_type);
+        assert_eq!(policy.min_duration, minutes(30));
+        assert_eq!(policy.max_duration, minutes(60));
+        assert!(policy.slot_alignment.is_none());
+        assert!(BookingPolicy::default().is_empty());
+    }
+
+    #[test]
+    fn invalid_policy_should_fail() {
+        let policy = BookingPolicy {
+            min_duration: minutes(60),
+            max_duration: minutes(30),
+            ..Default::default()
+        };
+        assert_eq!(
+            policy.validate().unwrap_err(),
+            Error::InvalidDuration("max_duration".to_string())
+        );
+
+        let policy = BookingPolicy {
+            slot_alignment: minutes(0),
+            ..Default::default()
+        };
+        assert_eq!(
+            policy.validate().unwrap_err(),
+            Error::InvalidDuration("slot_alignment".to_string())
+        );
+
+        let policy = BookingPolicy {
+            min_lead_time: minutes(-5),
+            ..Default::default()
+        };
+        assert_eq!(
+            policy.validate().unwrap_err(),
+            Error::InvalidDuration("min_lead_time".to_string())
+        );
+    }
+}
@@ -1,6 +1,12 @@
+use prost_types::{FieldMask, Timestamp};
+
 use crate::{
-    CancelRequest, ConfirmRequest, FilterRequest, GetRequest, QueryRequest, Reservation,
-    ReservationFilter, ReservationQuery, ReserveRequest, UpdateRequest,
+    bulk_request::Target, ApproveRequest, Attendee, AvailabilityRequest, BulkRequest, BulkResult,
+    CancelRequest, ConfirmRequest, FilterRequest, GetRequest, JoinWaitlistRequest,
+    LeaveWaitlistRequest, QueryRequest, RejectRequest, RescheduleRequest, Reservation,
+    ReservationFilter, ReservationIds, ReservationQuery, ReserveBatchRequest, ReserveRequest,
+    Resource, ResourceType, SetResourceRequest, SetResourceTypeRequest, UpdateAttendeesRequest,
+    UpdateRequest,
 };
 
 macro_rules! impl_new {
@@ -22,15 +28,159 @@ macro_rules! impl_new {
     };
 }
 
+/// requests which can be made conditional on the version of the reservation
+macro_rules! impl_versioned {
+    ($name:ident) => {
+        impl $name {
+            pub fn new(id: i64) -> Self {
+                Self {
+                    id,
+                    expected_version: None,
+                }
+            }
+
+            pub fn with_version(mut self, version: i64) -> Self {
+                self.expected_version = Some(version);
+                self
+            }
+        }
+    };
+}
+
+impl_versioned!(ConfirmRequest);
+impl_versioned!(CancelRequest);
+
 impl_new!(QueryRequest, query, ReservationQuery);
 impl_new!(FilterRequest, filter, ReservationFilter);
-impl_new!(ReserveRequest, reservation, Reservation);
-impl_new!(ConfirmRequest);
 impl_new!(GetRequest);
-impl_new!(CancelRequest);
+impl_new!(JoinWaitlistRequest, reservation, Reservation);
+impl_new!(LeaveWaitlistRequest);
+impl_new!(SetResourceRequest, resource, Resource);
+impl_new!(SetResourceTypeRequest, resource_type, ResourceType);
 
 impl UpdateRequest {
-    pub fn new(id: i64, note: String) -> Self {
-        Self { id, note }
+    pub fn new(id: i64, reservation: Reservation, paths: &[&str]) -> Self {
+        Self {
+            id,
+            reservation: Some(reservation),
+            update_mask: Some(FieldMask {
+                paths: paths.iter().map(|p| p.to_string()).collect(),
+            }),
+            expected_version: None,
+        }
+    }
+
+    pub fn with_version(mut self, version: i64) -> Self {
+        self.expected_version = Some(version);
+        self
+    }
+}
+
+impl ApproveRequest {
+    pub fn new(id: i64, approver: impl Into<String>) -> Self {
+        Self {
+            id,
+            approver: approver.into(),
+        }
+    }
+}
+
+impl RejectRequest {
+    pub fn new(id: i64, approver: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self {
+            id,
+            approver: approver.into(),
+            reason: reason.into(),
+        }
+    }
+}
+
+impl UpdateAttendeesRequest {
+    pub fn new(
+        id: i64,
+        user_id: impl Into<String>,
+        add: Vec<Attendee>,
+        remove: Vec<String>,
+    ) -> Self {
+        Self {
+            id,
+            user_id: user_id.into(),
+            add,
+            remove,
+            expected_version: None,
+        }
+    }
+}
+
+impl ReserveRequest {
+    pub fn new(reservation: Reservation) -> Self {
+        Self {
+            reservation: Some(reservation),
+            max_suggestions: 0,
+        }
+    }
+
+    pub fn with_suggestions(mut self, max: u32) -> Self {
+        self.max_suggestions = max;
+        self
+    }
+}
+
+impl ReserveBatchRequest {
+    pub fn new(reservations: Vec<Reservation>) -> Self {
+        Self { reservations }
+    }
+}
+
+impl RescheduleRequest {
+    pub fn new(id: i64, start: Timestamp, end: Timestamp, resource_id: Option<String>) -> Self {
+        Self {
+            id,
+            start: Some(start),
+            end: Some(end),
+            resource_id,
+        }
+    }
+}
+
+impl AvailabilityRequest {
+    pub fn new(resource_id: impl Into<String>, start: Timestamp, end: Timestamp) -> Self {
+        Self {
+            resource_id: resource_id.into(),
+            start: Some(start),
+            end: Some(end),
+        }
+    }
+}
+
+impl BulkRequest {
+    pub fn with_ids(ids: Vec<i64>) -> Self {
+        Self {
+            target: Some(Target::Ids(ReservationIds { ids })),
+        }
+    }
+
+    pub fn with_query(query: ReservationQuery) -> Self {
+        Self {
+            target: Some(Target::Query(query)),
+        }
+    }
+}
+
+impl BulkResult {
+    pub fn ok(reservation: Reservation) -> Self {
+        Self {
+            id: reservation.id,
+            reservation: Some(reservation),
+            error: String::new(),
+        }
+    }
+
+    pub fn err(id: i64, error: impl ToString) -> Self {
+        Self {
+            id,
+            reservation: None,
+            error: error.to_string(),
+        }
     }
 }
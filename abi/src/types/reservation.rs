@@ -1,13 +1,23 @@
-use chrono::{DateTime, FixedOffset, Utc};
+use std::{collections::HashMap, ops::Bound};
+
+use chrono::{
+    DateTime, Days, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
+};
+use chrono_tz::Tz;
+use prost_types::{FieldMask, Timestamp};
 use sqlx::{
     postgres::{types::PgRange, PgRow},
+    types::Json,
     FromRow, Row,
 };
-use std::ops::Bound;
 
-use super::{get_timespan, validate_range};
+use super::{
+    attendee::validate_attendees, get_timespan, parse_time_zone, validate_metadata, validate_range,
+    NaiveRange,
+};
 use crate::{
-    convert_to_timestamp, Error, Id, Reservation, ReservationStatus, RsvpStatus, Validator,
+    convert_to_timestamp, convert_to_utc_time, Attendee, Error, Id, Reservation, ReservationStatus,
+    RsvpStatus, Validator,
 };
 
 impl Reservation {
@@ -26,12 +36,245 @@ impl Reservation {
             end: Some(convert_to_timestamp(&end.with_timezone(&Utc))),
             note: note.into(),
             status: ReservationStatus::Pending as i32,
+            metadata: HashMap::new(),
+            created_at: None,
+            updated_at: None,
+            version: 0,
+            attendees: vec![],
+            time_zone: String::new(),
+            check_in: String::new(),
+            check_out: String::new(),
         }
     }
 
-    pub fn get_timespan(&self) -> PgRange<DateTime<Utc>> {
+    /// a whole day reservation from check_in up to check_out, its window is set from the
+    /// dates in the time zone of the resource when it is reserved
+    pub fn new_dated(
+        user_id: impl Into<String>,
+        resource_id: impl Into<String>,
+        check_in: NaiveDate,
+        check_out: NaiveDate,
+        note: impl Into<String>,
+    ) -> Self {
+        Self {
+            user_id: user_id.into(),
+            resource_id: resource_id.into(),
+            note: note.into(),
+            status: ReservationStatus::Pending as i32,
+            check_in: check_in.format(DATE_FORMAT).to_string(),
+            check_out: check_out.format(DATE_FORMAT).to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// whether the reservation books whole days
+    pub fn is_dated(&self) -> bool {
+        !self.check_in.is_empty() || !self.check_out.is_empty()
+    }
+
+    /// the check in and check out dates of a whole day reservation, None for a timed one
+    pub fn get_dates(&self) -> Result<Option<(NaiveDate, NaiveDate)>, Error> {
+        if !self.is_dated() {
+            return Ok(None);
+        }
+        let err = || Error::InvalidDateRange(format!("{}..{}", self.check_in, self.check_out));
+        let parse = |d: &str| NaiveDate::parse_from_str(d, DATE_FORMAT).map_err(|_| err());
+        let (check_in, check_out) = (parse(&self.check_in)?, parse(&self.check_out)?);
+        if check_in >= check_out {
+            return Err(err());
+        }
+        Ok(Some((check_in, check_out)))
+    }
+
+    /// set the window of a whole day reservation to local midnight of its dates in tz,
+    /// which becomes the zone of the reservation. a timed one is left alone
+    pub fn normalize_dates(&mut self, tz: &Tz) -> Result<(), Error> {
+        let (check_in, check_out) = match self.get_dates()? {
+            Some(dates) => dates,
+            None => return Ok(()),
+        };
+        let midnight = |d: NaiveDate| from_local(tz, d.and_time(NaiveTime::MIN));
+        self.start = Some(convert_to_timestamp(&midnight(check_in)));
+        self.end = Some(convert_to_timestamp(&midnight(check_out)));
+        self.time_zone = tz.name().to_string();
+        Ok(())
+    }
+
+    /// the dates as stored, None for a timed reservation
+    pub fn get_daterange(&self) -> Result<Option<PgRange<NaiveDate>>, Error> {
+        Ok(self.get_dates()?.map(|(check_in, check_out)| PgRange {
+            start: Bound::Included(check_in),
+            end: Bound::Excluded(check_out),
+        }))
+    }
+
+    /// the IANA time zone the reservation's wall clock times are kept in
+    pub fn with_time_zone(mut self, time_zone: impl Into<String>) -> Self {
+        self.time_zone = time_zone.into();
+        self
+    }
+
+    /// the zone of the reservation, UTC if it has none
+    pub fn get_time_zone(&self) -> Result<Tz, Error> {
+        parse_time_zone(&self.time_zone)
+    }
+
+    /// count occurrences of the reservation, every_days apart, the first one is self.
+    ///
+    /// occurrences keep the wall clock start and end in the reservation's time zone, a
+    /// 9am meeting stays at 9am across DST changes. a time skipped by DST moves an hour
+    /// forward, a repeated one takes the earlier instant
+    pub fn recur(&self, every_days: u32, count: usize) -> Result<Vec<Reservation>, Error> {
+        let dates = self.get_dates()?;
+        if dates.is_none() {
+            validate_range(self.start.as_ref(), self.end.as_ref())?;
+        }
+        if every_days == 0 {
+            return Err(Error::InvalidRecurrence(every_days));
+        }
+        let tz = self.get_time_zone()?;
+        let local = |ts: Option<&Timestamp>| -> Result<_, Error> {
+            ts.map(|ts| Ok(convert_to_utc_time(ts)?.with_timezone(&tz).naive_local()))
+                .transpose()
+        };
+        let start = local(self.start.as_ref())?;
+        let end = local(self.end.as_ref())?;
+
+        (0..count)
+            .map(|i| {
+                // a shift past the supported dates is an invalid window, not a panic
+                let shift = (every_days as u64)
+                    .checked_mul(i as u64)
+                    .map(Days::new)
+                    .ok_or(Error::InvalidTimespan)?;
+                let at = |t: Option<NaiveDateTime>| -> Result<_, Error> {
+                    t.map(|t| {
+                        let t = t.checked_add_days(shift).ok_or(Error::InvalidTimespan)?;
+                        Ok(convert_to_timestamp(&from_local(&tz, t)))
+                    })
+                    .transpose()
+                };
+                let mut rsvp = Reservation {
+                    id: 0,
+                    start: at(start)?,
+                    end: at(end)?,
+                    created_at: None,
+                    updated_at: None,
+                    version: 0,
+                    ..self.clone()
+                };
+                if let Some((check_in, check_out)) = dates {
+                    let day = |d: NaiveDate| -> Result<_, Error> {
+                        let d = d.checked_add_days(shift).ok_or(Error::InvalidTimespan)?;
+                        Ok(d.format(DATE_FORMAT).to_string())
+                    };
+                    rsvp.check_in = day(check_in)?;
+                    rsvp.check_out = day(check_out)?;
+                }
+                Ok(rsvp)
+            })
+            .collect()
+    }
+
+    /// add a metadata entry
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn get_timespan(&self) -> Result<PgRange<DateTime<Utc>>, Error> {
         get_timespan(self.start.as_ref(), self.end.as_ref())
     }
+
+    /// copy the fields listed in mask from other into self, id and status can't be updated.
+    /// a new owner becomes the organizer of the attendees
+    pub fn apply_update_mask(
+        &mut self,
+        mut other: Reservation,
+        mask: &FieldMask,
+    ) -> Result<(), Error> {
+        if mask.paths.is_empty() {
+            return Err(Error::MissingArgument("update_mask".to_string()));
+        }
+
+        // check all paths first, so an invalid mask never leaves self half updated
+        if let Some(path) = mask
+            .paths
+            .iter()
+            .find(|p| !UPDATABLE_PATHS.contains(&p.as_str()))
+        {
+            return Err(Error::InvalidUpdateMask(path.clone()));
+        }
+
+        let owner = self.user_id.clone();
+        for path in mask.paths.iter() {
+            match path.as_str() {
+                "user_id" => self.user_id = std::mem::take(&mut other.user_id),
+                "resource_id" => self.resource_id = std::mem::take(&mut other.resource_id),
+                "start" => self.start = other.start.take(),
+                "end" => self.end = other.end.take(),
+                "note" => self.note = std::mem::take(&mut other.note),
+                "metadata" => self.metadata = std::mem::take(&mut other.metadata),
+                "time_zone" => self.time_zone = std::mem::take(&mut other.time_zone),
+                "check_in" => self.check_in = std::mem::take(&mut other.check_in),
+                "check_out" => self.check_out = std::mem::take(&mut other.check_out),
+                _ => unreachable!(),
+            }
+        }
+
+        // the organizer of a meeting is always its owner
+        self.transfer_organizer(&owner);
+
+        // a new window without new dates turns a whole day reservation into a timed one
+        let has = |path: &str| mask.paths.iter().any(|p| p == path);
+        if (has("start") || has("end")) && !has("check_in") && !has("check_out") {
+            self.check_in.clear();
+            self.check_out.clear();
+        }
+
+        Ok(())
+    }
+}
+
+/// fields of Reservation which can be changed by an UpdateRequest
+const UPDATABLE_PATHS: &[&str] = &[
+    "user_id",
+    "resource_id",
+    "start",
+    "end",
+    "note",
+    "metadata",
+    "time_zone",
+    "check_in",
+    "check_out",
+];
+
+/// how check in and check out dates are written
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// check in and check out as written in a Reservation, empty for a timed one
+pub(super) fn format_dates(dates: Option<PgRange<NaiveDate>>) -> (String, String) {
+    let dates = dates.map(NaiveRange::from);
+    let date = |d: Option<NaiveDate>| {
+        d.map(|d| d.format(DATE_FORMAT).to_string())
+            .unwrap_or_default()
+    };
+    (
+        date(dates.as_ref().and_then(|d| d.start)),
+        date(dates.and_then(|d| d.end)),
+    )
+}
+
+/// the instant of a wall clock time, a skipped time moves an hour forward
+fn from_local(tz: &Tz, time: NaiveDateTime) -> DateTime<Utc> {
+    tz.from_local_datetime(&time)
+        .earliest()
+        .or_else(|| {
+            let later = time.checked_add_signed(Duration::hours(1))?;
+            tz.from_local_datetime(&later).earliest()
+        })
+        .unwrap_or_else(|| Utc.from_utc_datetime(&time).with_timezone(tz))
+        .with_timezone(&Utc)
 }
 
 impl Id for Reservation {
@@ -50,6 +293,11 @@ impl Validator for Reservation {
             return Err(Error::InvalidResourceId(self.resource_id.clone()));
         }
 
+        self.get_time_zone()?;
+        self.get_dates()?;
+        validate_metadata(&self.metadata)?;
+        validate_attendees(&self.user_id, &self.attendees)?;
+
         validate_range(self.start.as_ref(), self.end.as_ref())
     }
 }
@@ -62,13 +310,16 @@ impl FromRow<'_, PgRow> for Reservation {
         let range: NaiveRange<DateTime<Utc>> = range.into();
 
         // in real world, reservation will always have a bound
-        assert!(range.start.is_some());
-        assert!(range.end.is_some());
-
-        let start = range.start.unwrap();
-        let end = range.end.unwrap();
+        let unbounded = || sqlx::Error::Decode("reservation timespan is unbounded".into());
+        let start = range.start.ok_or_else(unbounded)?;
+        let end = range.end.ok_or_else(unbounded)?;
 
         let status: RsvpStatus = row.get("status");
+        let metadata: Json<HashMap<String, String>> = row.get("metadata");
+        let attendees: Json<Vec<Attendee>> = row.get("attendees");
+        let (check_in, check_out) = format_dates(row.get("dates"));
+        let created_at: DateTime<Utc> = row.get("created_at");
+        let updated_at: DateTime<Utc> = row.get("updated_at");
 
         Ok(Self {
             id,
@@ -78,25 +329,234 @@ impl FromRow<'_, PgRow> for Reservation {
             start: Some(convert_to_timestamp(&start)),
             end: Some(convert_to_timestamp(&end)),
             note: row.get("note"),
+            metadata: metadata.0,
+            created_at: Some(convert_to_timestamp(&created_at)),
+            updated_at: Some(convert_to_timestamp(&updated_at)),
+            version: row.get("version"),
+            attendees: attendees.0,
+            time_zone: row.get("time_zone"),
+            check_in,
+            check_out,
         })
     }
 }
 
-struct NaiveRange<T> {
-    start: Option<T>,
-    end: Option<T>,
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AttendeeRole;
+
+    fn mask(paths: &[&str]) -> FieldMask {
+        FieldMask {
+            paths: paths.iter().map(|p| p.to_string()).collect(),
+        }
+    }
 
-impl<T> From<PgRange<T>> for NaiveRange<T> {
-    fn from(range: PgRange<T>) -> Self {
-        let f = |b: Bound<T>| match b {
-            Bound::Included(v) => Some(v),
-            Bound::Excluded(v) => Some(v),
-            Bound::Unbounded => None,
+    fn test_reservation() -> Reservation {
+        Reservation::new(
+            "test-user",
+            "test-resource",
+            "2023-01-01T10:10:10-0700".parse().unwrap(),
+            "2023-01-04T10:10:10-0700".parse().unwrap(),
+            "test-note",
+        )
+    }
+
+    #[test]
+    fn apply_update_mask_should_only_change_masked_fields() {
+        let mut rsvp = test_reservation();
+        let other = Reservation::new(
+            "other-user",
+            "other-resource",
+            "2023-01-02T10:10:10-0700".parse().unwrap(),
+            "2023-01-05T10:10:10-0700".parse().unwrap(),
+            "other-note",
+        );
+
+        rsvp.apply_update_mask(other.clone(), &mask(&["note", "end"]))
+            .unwrap();
+        assert_eq!(rsvp.user_id, "test-user");
+        assert_eq!(rsvp.resource_id, "test-resource");
+        assert_eq!(rsvp.start, test_reservation().start);
+        assert_eq!(rsvp.end, other.end);
+        assert_eq!(rsvp.note, "other-note");
+    }
+
+    #[test]
+    fn apply_update_mask_should_move_the_organizer_to_a_new_owner() {
+        let mut rsvp = Reservation {
+            attendees: vec![
+                Attendee::new("test-user", AttendeeRole::Organizer),
+                Attendee::new("other-user", AttendeeRole::Optional),
+            ],
+            ..test_reservation()
+        };
+        let other = Reservation {
+            user_id: "other-user".to_string(),
+            ..Default::default()
         };
-        let start = f(range.start);
-        let end = f(range.end);
 
-        Self { start, end }
+        rsvp.apply_update_mask(other, &mask(&["user_id"])).unwrap();
+        rsvp.validate().unwrap();
+        assert_eq!(rsvp.attendees[0].role, AttendeeRole::Required as i32);
+        assert_eq!(rsvp.attendees[1].role, AttendeeRole::Organizer as i32);
+    }
+
+    #[test]
+    fn apply_update_mask_should_reject_invalid_paths() {
+        let mut rsvp = test_reservation();
+
+        let err = rsvp
+            .apply_update_mask(Reservation::default(), &mask(&[]))
+            .unwrap_err();
+        assert_eq!(err, Error::MissingArgument("update_mask".to_string()));
+
+        let err = rsvp
+            .apply_update_mask(Reservation::default(), &mask(&["note", "status"]))
+            .unwrap_err();
+        assert_eq!(err, Error::InvalidUpdateMask("status".to_string()));
+        assert_eq!(rsvp, test_reservation());
+    }
+
+    #[test]
+    fn recur_should_keep_wall_clock_time_across_dst() {
+        // berlin switches to summer time on 2023-03-26
+        let rsvp = Reservation::new(
+            "test-user",
+            "test-resource",
+            "2023-03-24T09:00:00+0100".parse().unwrap(),
+            "2023-03-24T10:00:00+0100".parse().unwrap(),
+            "standup",
+        )
+        .with_time_zone("Europe/Berlin");
+
+        let rsvps = rsvp.recur(1, 4).unwrap();
+        let starts: Vec<_> = rsvps
+            .iter()
+            .map(|r| {
+                convert_to_utc_time(r.start.as_ref().unwrap())
+                    .unwrap()
+                    .to_rfc3339()
+            })
+            .collect();
+        assert_eq!(
+            starts,
+            vec![
+                "2023-03-24T08:00:00+00:00",
+                "2023-03-25T08:00:00+00:00",
+                "2023-03-26T07:00:00+00:00",
+                "2023-03-27T07:00:00+00:00",
+            ]
+        );
+        assert!(rsvps.iter().all(|r| r.time_zone == "Europe/Berlin"));
+
+        // the same meeting kept in UTC drifts to 10am local
+        let utc = rsvp.clone().with_time_zone("").recur(1, 3).unwrap();
+        assert_eq!(
+            convert_to_utc_time(utc[2].start.as_ref().unwrap())
+                .unwrap()
+                .to_rfc3339(),
+            "2023-03-26T08:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn dated_reservation_should_start_at_local_midnight() {
+        let date = |d: &str| d.parse::<NaiveDate>().unwrap();
+        let mut rsvp = Reservation::new_dated(
+            "test-user",
+            "room-101",
+            date("2023-03-25"),
+            date("2023-03-27"),
+            "two nights",
+        );
+        assert!(rsvp.is_dated());
+        assert_eq!(rsvp.validate().unwrap_err(), Error::InvalidTimespan);
+
+        // the second night is an hour shorter in berlin
+        rsvp.normalize_dates(&"Europe/Berlin".parse().unwrap())
+            .unwrap();
+        rsvp.validate().unwrap();
+        assert_eq!(rsvp.time_zone, "Europe/Berlin");
+        let utc = |ts: &Option<Timestamp>| {
+            convert_to_utc_time(ts.as_ref().unwrap())
+                .unwrap()
+                .to_rfc3339()
+        };
+        assert_eq!(utc(&rsvp.start), "2023-03-24T23:00:00+00:00");
+        assert_eq!(utc(&rsvp.end), "2023-03-26T22:00:00+00:00");
+
+        let week = rsvp.recur(7, 2).unwrap();
+        assert_eq!(week[1].check_in, "2023-04-01");
+        assert_eq!(week[1].check_out, "2023-04-03");
+        assert_eq!(utc(&week[1].start), "2023-03-31T22:00:00+00:00");
+
+        // new start or end without new dates makes it timed
+        rsvp.apply_update_mask(test_reservation(), &mask(&["end"]))
+            .unwrap();
+        assert!(!rsvp.is_dated());
+    }
+
+    #[test]
+    fn get_dates_should_reject_bad_dates() {
+        let rsvp = Reservation {
+            check_in: "2023-03-27".to_string(),
+            check_out: "2023-03-27".to_string(),
+            ..test_reservation()
+        };
+        let err = Error::InvalidDateRange("2023-03-27..2023-03-27".to_string());
+        assert_eq!(rsvp.get_dates().unwrap_err(), err);
+        assert_eq!(rsvp.validate().unwrap_err(), err);
+
+        let rsvp = Reservation {
+            check_in: "2023-03-27".to_string(),
+            ..test_reservation()
+        };
+        assert_eq!(
+            rsvp.get_dates().unwrap_err(),
+            Error::InvalidDateRange("2023-03-27..".to_string())
+        );
+        assert_eq!(test_reservation().get_dates().unwrap(), None);
+    }
+
+    #[test]
+    fn recur_should_handle_skipped_times_and_bad_input() {
+        // 02:30 doesn't exist in berlin on 2023-03-26
+        let rsvp = Reservation::new(
+            "test-user",
+            "test-resource",
+            "2023-03-25T02:30:00+0100".parse().unwrap(),
+            "2023-03-25T04:00:00+0100".parse().unwrap(),
+            "night shift",
+        )
+        .with_time_zone("Europe/Berlin");
+        let rsvps = rsvp.recur(1, 2).unwrap();
+        assert_eq!(
+            rsvps[1].start,
+            Some(convert_to_timestamp(
+                &"2023-03-26T01:30:00Z".parse::<DateTime<Utc>>().unwrap()
+            ))
+        );
+
+        assert_eq!(rsvp.recur(0, 2).unwrap_err(), Error::InvalidRecurrence(0));
+        assert_eq!(rsvp.recur(u32::MAX, 2).unwrap_err(), Error::InvalidTimespan);
+        assert_eq!(
+            Reservation::new_dated(
+                "test-user",
+                "room-101",
+                NaiveDate::MAX - Days::new(1),
+                NaiveDate::MAX,
+                "forever",
+            )
+            .recur(1, 2)
+            .unwrap_err(),
+            Error::InvalidTimespan
+        );
+        assert_eq!(
+            rsvp.with_time_zone("Mars/Olympus_Mons")
+                .recur(1, 2)
+                .unwrap_err(),
+            Error::InvalidTimeZone("Mars/Olympus_Mons".to_string())
+        );
     }
 }
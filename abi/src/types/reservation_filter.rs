@@ -1,3 +1,4 @@
+use super::validate_metadata;
 use crate::{
     Error, FilterPager, Id, Normalizer, PageInfo, Pager, Paginator, ReservationFilter,
     ReservationStatus, ToSql, Validator,
@@ -41,6 +42,7 @@ impl Validator for ReservationFilter {
         }
 
         ReservationStatus::from_i32(self.status).ok_or(Error::InvalidStatus(self.status))?;
+        validate_metadata(&self.metadata)?;
 
         Ok(())
     }
@@ -55,7 +57,7 @@ impl Normalizer for ReservationFilter {
 }
 
 impl ToSql for ReservationFilter {
-    fn to_sql(&self) -> String {
+    fn to_sql(&self) -> Result<String, Error> {
         let middle_plus = if self.cursor.is_some() { 1 } else { 0 };
         let limit = self.page_size + 1 + middle_plus;
 
@@ -66,22 +68,16 @@ impl ToSql for ReservationFilter {
         };
         let status = self.get_status();
 
-        let user_resource_condition = match (self.user_id.is_empty(), self.resource_id.is_empty()) {
-            (true, true) => "TRUE".into(),
-            (true, false) => format!("resource_id = '{}'", self.resource_id),
-            (false, true) => format!("user_id = '{}'", self.user_id),
-            (false, false) => format!(
-                "user_id = '{}' AND resource_id = '{}'",
-                self.user_id, self.resource_id
-            ),
-        };
+        // user and resource ids are bound, an empty one matches everything.
+        // attendees find the reservations they take part in by their own user id
+        let user_resource_condition = "($3 = '' OR user_id = $3 OR attendees @> jsonb_build_array(jsonb_build_object('user_id', $3::text))) AND ($4 = '' OR resource_id = $4)";
 
         let order = if self.desc { "DESC" } else { "ASC" };
 
-        format!(
-            "SELECT * FROM rsvp.reservations WHERE {} AND status = '{}'::rsvp.reservation_status AND {} ORDER BY id {} LIMIT {}",
+        Ok(format!(
+            "SELECT * FROM rsvp.reservations WHERE tenant_id = $1 AND {} AND status = '{}'::rsvp.reservation_status AND {} AND metadata @> $2 ORDER BY id {} LIMIT {}",
             user_resource_condition, status, cursor_condition, order, limit
-        )
+        ))
     }
 }
 
@@ -239,10 +235,10 @@ mod tests {
 
         filter.normalize().unwrap();
 
-        let sql = filter.to_sql();
+        let sql = filter.to_sql().unwrap();
         assert_eq!(
             sql,
-            "SELECT * FROM rsvp.reservations WHERE TRUE AND status = 'pending'::rsvp.reservation_status AND id <= 5 ORDER BY id DESC LIMIT 15"
+            "SELECT * FROM rsvp.reservations WHERE tenant_id = $1 AND ($3 = '' OR user_id = $3 OR attendees @> jsonb_build_array(jsonb_build_object('user_id', $3::text))) AND ($4 = '' OR resource_id = $4) AND status = 'pending'::rsvp.reservation_status AND id <= 5 AND metadata @> $2 ORDER BY id DESC LIMIT 15"
         );
 
         let mut filter = ReservationFilterBuilder::default()
@@ -254,10 +250,10 @@ mod tests {
             .unwrap();
         filter.normalize().unwrap();
 
-        let sql = filter.to_sql();
+        let sql = filter.to_sql().unwrap();
         assert_eq!(
             sql,
-            "SELECT * FROM rsvp.reservations WHERE user_id = 'test-uid-1' AND status = 'pending'::rsvp.reservation_status AND id >= 2 ORDER BY id ASC LIMIT 14"
+            "SELECT * FROM rsvp.reservations WHERE tenant_id = $1 AND ($3 = '' OR user_id = $3 OR attendees @> jsonb_build_array(jsonb_build_object('user_id', $3::text))) AND ($4 = '' OR resource_id = $4) AND status = 'pending'::rsvp.reservation_status AND id >= 2 AND metadata @> $2 ORDER BY id ASC LIMIT 14"
         );
 
         let mut filter = ReservationFilterBuilder::default()
@@ -268,10 +264,10 @@ mod tests {
             .unwrap();
         filter.normalize().unwrap();
 
-        let sql = filter.to_sql();
+        let sql = filter.to_sql().unwrap();
         assert_eq!(
             sql,
-            "SELECT * FROM rsvp.reservations WHERE user_id = 'test-uid-1' AND status = 'pending'::rsvp.reservation_status AND id <= 9223372036854775807 ORDER BY id DESC LIMIT 13"
+            "SELECT * FROM rsvp.reservations WHERE tenant_id = $1 AND ($3 = '' OR user_id = $3 OR attendees @> jsonb_build_array(jsonb_build_object('user_id', $3::text))) AND ($4 = '' OR resource_id = $4) AND status = 'pending'::rsvp.reservation_status AND id <= 9223372036854775807 AND metadata @> $2 ORDER BY id DESC LIMIT 13"
         );
 
         let mut filter = ReservationFilterBuilder::default()
@@ -282,8 +278,8 @@ mod tests {
             .unwrap();
         filter.normalize().unwrap();
         assert_eq!(
-            filter.to_sql(),
-            "SELECT * FROM rsvp.reservations WHERE user_id = 'test-uid-1' AND status = 'pending'::rsvp.reservation_status AND id >= 0 ORDER BY id ASC LIMIT 13"
+            filter.to_sql().unwrap(),
+            "SELECT * FROM rsvp.reservations WHERE tenant_id = $1 AND ($3 = '' OR user_id = $3 OR attendees @> jsonb_build_array(jsonb_build_object('user_id', $3::text))) AND ($4 = '' OR resource_id = $4) AND status = 'pending'::rsvp.reservation_status AND id >= 0 AND metadata @> $2 ORDER BY id ASC LIMIT 13"
         );
     }
 }
@@ -1,7 +1,11 @@
+use std::cmp::Ordering;
+
 use prost_types::Timestamp;
 
+use super::validate_metadata;
 use crate::{
-    convert_to_utc_time, Error, Normalizer, ReservationQuery, ReservationStatus, ToSql, Validator,
+    cmp_timestamp, convert_to_utc_time, Error, Normalizer, ReservationQuery, ReservationStatus,
+    ToSql, Validator,
 };
 
 impl ReservationQuery {
@@ -13,9 +17,13 @@ impl ReservationQuery {
 impl Validator for ReservationQuery {
     fn validate(&self) -> Result<(), Error> {
         ReservationStatus::from_i32(self.status).ok_or(Error::InvalidStatus(self.status))?;
+        validate_metadata(&self.metadata)?;
 
+        for ts in self.start.iter().chain(self.end.iter()) {
+            convert_to_utc_time(ts)?;
+        }
         if let (Some(start), Some(end)) = (self.start.as_ref(), self.end.as_ref()) {
-            if start.seconds > end.seconds {
+            if cmp_timestamp(start, end) == Ordering::Greater {
                 return Err(Error::InvalidTimespan);
             }
         }
@@ -33,35 +41,31 @@ impl Normalizer for ReservationQuery {
 }
 
 impl ToSql for ReservationQuery {
-    fn to_sql(&self) -> String {
-        let status = ReservationStatus::from_i32(self.status).unwrap();
+    fn to_sql(&self) -> Result<String, Error> {
+        let status =
+            ReservationStatus::from_i32(self.status).ok_or(Error::InvalidStatus(self.status))?;
 
         let timespan = format!(
             "tstzrange('{}', '{}')",
-            get_time_string(self.start.as_ref(), true),
-            get_time_string(self.end.as_ref(), false),
+            get_time_string(self.start.as_ref(), true)?,
+            get_time_string(self.end.as_ref(), false)?,
         );
 
-        let condition = match (self.user_id.is_empty(), self.resource_id.is_empty()) {
-            (true, true) => "TRUE".into(),
-            (false, true) => format!("user_id = '{}'", self.user_id),
-            (true, false) => format!("resource_id = '{}'", self.resource_id),
-            (false, false) => format!(
-                "user_id = '{}' AND resource_id = '{}'",
-                self.user_id, self.resource_id
-            ),
-        };
+        // user and resource ids are bound, an empty one matches everything
+        let condition = "($3 = '' OR user_id = $3) AND ($4 = '' OR resource_id = $4)";
 
         let direction = if !self.desc { "ASC" } else { "DESC" };
 
-        format!("SELECT * FROM rsvp.reservations WHERE {} @> timespan AND status = '{}'::rsvp.reservation_status AND {} ORDER BY lower(timespan) {}", timespan, status, condition, direction)
+        Ok(format!("SELECT * FROM rsvp.reservations WHERE tenant_id = $1 AND {} @> timespan AND status = '{}'::rsvp.reservation_status AND {} AND metadata @> $2 ORDER BY lower(timespan) {}", timespan, status, condition, direction))
     }
 }
 
-fn get_time_string(ts: Option<&Timestamp>, start: bool) -> String {
-    match ts {
-        Some(ts) => convert_to_utc_time(ts).to_rfc3339(),
-        None => (if start { "-infinity" } else { "infinity" }).into(),
+/// a missing timestamp is unbounded, one which can't be converted is an invalid timespan
+fn get_time_string(ts: Option<&Timestamp>, start: bool) -> Result<String, Error> {
+    match ts.map(convert_to_utc_time) {
+        Some(Ok(ts)) => Ok(ts.to_rfc3339()),
+        Some(Err(_)) => Err(Error::InvalidTimespan),
+        None => Ok((if start { "-infinity" } else { "infinity" }).into()),
     }
 }
 
@@ -88,8 +92,8 @@ mod tests {
         query.do_normalize();
 
         assert_eq!(
-            query.to_sql(),
-            "SELECT * FROM rsvp.reservations WHERE tstzrange('1970-01-01T00:00:00+00:00', '1970-01-01T00:00:01+00:00') @> timespan AND status = 'pending'::rsvp.reservation_status AND user_id = 'user' AND resource_id = 'resource' ORDER BY lower(timespan) ASC"
+            query.to_sql().unwrap(),
+            "SELECT * FROM rsvp.reservations WHERE tenant_id = $1 AND tstzrange('1970-01-01T00:00:00+00:00', '1970-01-01T00:00:01+00:00') @> timespan AND status = 'pending'::rsvp.reservation_status AND ($3 = '' OR user_id = $3) AND ($4 = '' OR resource_id = $4) AND metadata @> $2 ORDER BY lower(timespan) ASC"
         );
 
         let query = ReservationQueryBuilder::default()
@@ -101,8 +105,8 @@ mod tests {
             .unwrap();
 
         assert_eq!(
-            query.to_sql(),
-            "SELECT * FROM rsvp.reservations WHERE tstzrange('-infinity', 'infinity') @> timespan AND status = 'pending'::rsvp.reservation_status AND user_id = 'user' AND resource_id = 'resource' ORDER BY lower(timespan) DESC"
+            query.to_sql().unwrap(),
+            "SELECT * FROM rsvp.reservations WHERE tenant_id = $1 AND tstzrange('-infinity', 'infinity') @> timespan AND status = 'pending'::rsvp.reservation_status AND ($3 = '' OR user_id = $3) AND ($4 = '' OR resource_id = $4) AND metadata @> $2 ORDER BY lower(timespan) DESC"
         );
 
         let query = ReservationQueryBuilder::default()
@@ -117,8 +121,8 @@ mod tests {
             .build()
             .unwrap();
         assert_eq!(
-            query.to_sql(),
-            "SELECT * FROM rsvp.reservations WHERE tstzrange('1970-01-01T00:00:00+00:00', 'infinity') @> timespan AND status = 'pending'::rsvp.reservation_status AND user_id = 'user' AND resource_id = 'resource' ORDER BY lower(timespan) DESC"
+            query.to_sql().unwrap(),
+            "SELECT * FROM rsvp.reservations WHERE tenant_id = $1 AND tstzrange('1970-01-01T00:00:00+00:00', 'infinity') @> timespan AND status = 'pending'::rsvp.reservation_status AND ($3 = '' OR user_id = $3) AND ($4 = '' OR resource_id = $4) AND metadata @> $2 ORDER BY lower(timespan) DESC"
         );
 
         let query = ReservationQueryBuilder::default()
@@ -133,8 +137,21 @@ mod tests {
             .build()
             .unwrap();
         assert_eq!(
-            query.to_sql(),
-            "SELECT * FROM rsvp.reservations WHERE tstzrange('-infinity', '1970-01-01T00:00:01+00:00') @> timespan AND status = 'pending'::rsvp.reservation_status AND user_id = 'user' AND resource_id = 'resource' ORDER BY lower(timespan) DESC"
+            query.to_sql().unwrap(),
+            "SELECT * FROM rsvp.reservations WHERE tenant_id = $1 AND tstzrange('-infinity', '1970-01-01T00:00:01+00:00') @> timespan AND status = 'pending'::rsvp.reservation_status AND ($3 = '' OR user_id = $3) AND ($4 = '' OR resource_id = $4) AND metadata @> $2 ORDER BY lower(timespan) DESC"
         );
     }
+
+    #[test]
+    fn query_with_bad_timestamp_should_be_invalid() {
+        let query = ReservationQueryBuilder::default()
+            .start(Timestamp {
+                seconds: i64::MAX,
+                nanos: 0,
+            })
+            .build()
+            .unwrap();
+        assert_eq!(query.to_sql().unwrap_err(), Error::InvalidTimespan);
+        assert_eq!(query.validate().unwrap_err(), Error::InvalidTimespan);
+    }
 }
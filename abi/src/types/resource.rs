@@ -0,0 +1,236 @@
+use chrono::{NaiveDate, NaiveTime, Weekday};
+use chrono_tz::Tz;
+use sqlx::{
+    postgres::{types::PgInterval, PgRow},
+    types::Json,
+    FromRow, Row,
+};
+
+use super::parse_time_zone;
+use crate::{
+    convert_to_duration, convert_to_interval, BookingPolicy, Error, OpeningHours, Resource,
+    Validator,
+};
+
+impl Resource {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn get_pre_buffer(&self) -> PgInterval {
+        convert_to_interval(&self.pre_buffer.clone().unwrap_or_default())
+    }
+
+    pub fn get_post_buffer(&self) -> PgInterval {
+        convert_to_interval(&self.post_buffer.clone().unwrap_or_default())
+    }
+
+    pub fn is_approver(&self, user_id: &str) -> bool {
+        self.approvers.iter().any(|a| a == user_id)
+    }
+
+    pub fn get_time_zone(&self) -> Result<Tz, Error> {
+        parse_time_zone(&self.time_zone)
+    }
+
+    pub fn get_blackout_dates(&self) -> Result<Vec<NaiveDate>, Error> {
+        self.blackout_dates
+            .iter()
+            .map(|d| {
+                NaiveDate::parse_from_str(d, "%Y-%m-%d")
+                    .map_err(|_| Error::InvalidBlackoutDate(d.clone()))
+            })
+            .collect()
+    }
+}
+
+impl OpeningHours {
+    pub fn new(weekday: Weekday, open: impl Into<String>, close: impl Into<String>) -> Self {
+        Self {
+            weekday: weekday.number_from_monday(),
+            open: open.into(),
+            close: close.into(),
+        }
+    }
+
+    pub fn get_weekday(&self) -> Result<Weekday, Error> {
+        match self.weekday {
+            1..=7 => Ok(Weekday::try_from(self.weekday as u8 - 1).unwrap()),
+            _ => Err(self.invalid()),
+        }
+    }
+
+    /// opening and closing time, a closing time of None is the end of the day
+    pub fn get_hours(&self) -> Result<(NaiveTime, Option<NaiveTime>), Error> {
+        let open = NaiveTime::parse_from_str(&self.open, "%H:%M").map_err(|_| self.invalid())?;
+        let close = match self.close.as_str() {
+            "24:00" => None,
+            s => Some(NaiveTime::parse_from_str(s, "%H:%M").map_err(|_| self.invalid())?),
+        };
+
+        if let Some(close) = close {
+            if close <= open {
+                return Err(self.invalid());
+            }
+        }
+
+        Ok((open, close))
+    }
+
+    fn invalid(&self) -> Error {
+        Error::InvalidOpeningHours(format!("{} {}-{}", self.weekday, self.open, self.close))
+    }
+}
+
+impl Validator for Resource {
+    fn validate(&self) -> Result<(), Error> {
+        if self.id.is_empty() {
+            return Err(Error::InvalidResourceId(self.id.clone()));
+        }
+
+        for (name, buffer) in [
+            ("pre_buffer", &self.pre_buffer),
+            ("post_buffer", &self.post_buffer),
+        ] {
+            if let Some(d) = buffer {
+                if d.seconds < 0 || d.nanos < 0 {
+                    return Err(Error::InvalidDuration(name.to_string()));
+                }
+            }
+        }
+
+        self.get_time_zone()?;
+        for hours in self.opening_hours.iter() {
+            hours.get_weekday()?;
+            hours.get_hours()?;
+        }
+        self.get_blackout_dates()?;
+        if let Some(policy) = &self.policy {
+            policy.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromRow<'_, PgRow> for Resource {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        let pre_buffer: PgInterval = row.get("pre_buffer");
+        let post_buffer: PgInterval = row.get("post_buffer");
+        let opening_hours: Json<Vec<OpeningHours>> = row.get("opening_hours");
+        let blackout_dates: Vec<NaiveDate> = row.get("blackout_dates");
+        let policy = BookingPolicy::from_row(row)?;
+
+        Ok(Self {
+            id: row.get("id"),
+            pre_buffer: Some(convert_to_duration(&pre_buffer)),
+            post_buffer: Some(convert_to_duration(&post_buffer)),
+            time_zone: row.get("time_zone"),
+            opening_hours: opening_hours.0,
+            blackout_dates: blackout_dates
+                .iter()
+                .map(|d| d.format("%Y-%m-%d").to_string())
+                .collect(),
+            resource_type: row.get("resource_type"),
+            requires_approval: row.get("requires_approval"),
+            approvers: row.get("approvers"),
+            policy: (!policy.is_empty()).then_some(policy),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost_types::Duration;
+
+    #[test]
+    fn resource_with_negative_buffer_should_fail() {
+        let resource = Resource {
+            id: "room".to_string(),
+            post_buffer: Some(Duration {
+                seconds: -60,
+                nanos: 0,
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            resource.validate().unwrap_err(),
+            Error::InvalidDuration("post_buffer".to_string())
+        );
+
+        assert_eq!(
+            Resource::default().validate().unwrap_err(),
+            Error::InvalidResourceId("".to_string())
+        );
+        assert!(Resource::new("room").validate().is_ok());
+    }
+
+    #[test]
+    fn resource_with_invalid_schedule_should_fail() {
+        let resource = Resource {
+            time_zone: "Mars/Olympus_Mons".to_string(),
+            ..Resource::new("room")
+        };
+        assert_eq!(
+            resource.validate().unwrap_err(),
+            Error::InvalidTimeZone("Mars/Olympus_Mons".to_string())
+        );
+
+        let resource = Resource {
+            time_zone: "Europe/Berlin".to_string(),
+            opening_hours: vec![
+                OpeningHours::new(Weekday::Mon, "09:00", "24:00"),
+                OpeningHours::new(Weekday::Tue, "18:00", "09:00"),
+            ],
+            ..Resource::new("room")
+        };
+        assert_eq!(
+            resource.validate().unwrap_err(),
+            Error::InvalidOpeningHours("2 18:00-09:00".to_string())
+        );
+
+        let resource = Resource {
+            opening_hours: vec![OpeningHours {
+                weekday: 8,
+                open: "09:00".to_string(),
+                close: "18:00".to_string(),
+            }],
+            ..Resource::new("room")
+        };
+        assert!(matches!(
+            resource.validate().unwrap_err(),
+            Error::InvalidOpeningHours(_)
+        ));
+
+        let resource = Resource {
+            blackout_dates: vec!["2023-12-25".to_string(), "2023-13-01".to_string()],
+            ..Resource::new("room")
+        };
+        assert_eq!(
+            resource.validate().unwrap_err(),
+            Error::InvalidBlackoutDate("2023-13-01".to_string())
+        );
+    }
+
+    #[test]
+    fn buffer_should_convert_to_interval_and_back() {
+        let d = Duration {
+            seconds: 90_061,
+            nanos: 5_000,
+        };
+        let interval = convert_to_interval(&d);
+        assert_eq!(interval.microseconds, 90_061_000_005);
+        assert_eq!(convert_to_duration(&interval), d);
+
+        let interval = PgInterval {
+            months: 0,
+            days: 1,
+            microseconds: 1_000_000,
+        };
+        assert_eq!(convert_to_duration(&interval).seconds, 86_401);
+    }
+}
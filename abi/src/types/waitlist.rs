@@ -0,0 +1,29 @@
+use chrono::{DateTime, Utc};
+use sqlx::{
+    postgres::{types::PgRange, PgRow},
+    FromRow, Row,
+};
+
+use super::{reservation::format_dates, NaiveRange};
+use crate::{convert_to_timestamp, WaitlistEntry};
+
+/// 从数据库中读取 waitlist entry, 与 Reservation 一样, timespan 总是有边界的
+impl FromRow<'_, PgRow> for WaitlistEntry {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        let range: PgRange<DateTime<Utc>> = row.get("timespan");
+        let range: NaiveRange<DateTime<Utc>> = range.into();
+        let (check_in, check_out) = format_dates(row.get("dates"));
+
+        Ok(Self {
+            id: row.get("id"),
+            user_id: row.get("user_id"),
+            resource_id: row.get("resource_id"),
+            start: range.start.map(|t| convert_to_timestamp(&t)),
+            end: range.end.map(|t| convert_to_timestamp(&t)),
+            note: row.get("note"),
+            time_zone: row.get("time_zone"),
+            check_in,
+            check_out,
+        })
+    }
+}
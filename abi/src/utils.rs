@@ -1,9 +1,41 @@
-use chrono::{DateTime, NaiveDateTime, Utc};
-use prost_types::Timestamp;
+use std::cmp::Ordering;
 
-pub fn convert_to_utc_time(ts: &Timestamp) -> DateTime<Utc> {
-    let naive = NaiveDateTime::from_timestamp_opt(ts.seconds, ts.nanos as _).unwrap();
-    DateTime::<Utc>::from_utc(naive, Utc)
+use chrono::{DateTime, Utc};
+use prost_types::{Duration, Timestamp};
+use sqlx::postgres::types::PgInterval;
+
+use crate::Error;
+
+/// 0001-01-01T00:00:00Z, the first second a protobuf timestamp can hold
+const MIN_TIMESTAMP_SECONDS: i64 = -62_135_596_800;
+
+/// 9999-12-31T23:59:59Z, the last second a protobuf timestamp can hold
+const MAX_TIMESTAMP_SECONDS: i64 = 253_402_300_799;
+
+/// a timestamp outside 0001-01-01 to 9999-12-31 or with nanos outside [0, 1e9) is invalid
+pub fn convert_to_utc_time(ts: &Timestamp) -> Result<DateTime<Utc>, Error> {
+    if !(MIN_TIMESTAMP_SECONDS..=MAX_TIMESTAMP_SECONDS).contains(&ts.seconds)
+        || !(0..1_000_000_000).contains(&ts.nanos)
+    {
+        return Err(Error::InvalidTimespan);
+    }
+    DateTime::<Utc>::from_timestamp(ts.seconds, ts.nanos as _).ok_or(Error::InvalidTimespan)
+}
+
+/// order timestamps by the instant they stand for, unnormalized ones included
+pub fn cmp_timestamp(a: &Timestamp, b: &Timestamp) -> Ordering {
+    let (mut a, mut b) = (a.clone(), b.clone());
+    a.normalize();
+    b.normalize();
+    (a.seconds, a.nanos).cmp(&(b.seconds, b.nanos))
+}
+
+/// postgres keeps microseconds, the rest of the nanos is dropped before a timestamp is stored
+pub fn truncate_to_micros(ts: &Timestamp) -> Timestamp {
+    let mut ts = ts.clone();
+    ts.normalize();
+    ts.nanos -= ts.nanos % 1_000;
+    ts
 }
 
 pub fn convert_to_timestamp(dt: &DateTime<Utc>) -> Timestamp {
@@ -12,3 +44,101 @@ pub fn convert_to_timestamp(dt: &DateTime<Utc>) -> Timestamp {
         nanos: dt.timestamp_subsec_nanos() as _,
     }
 }
+
+pub fn convert_to_interval(d: &Duration) -> PgInterval {
+    PgInterval {
+        months: 0,
+        days: 0,
+        microseconds: d
+            .seconds
+            .saturating_mul(1_000_000)
+            .saturating_add(d.nanos as i64 / 1_000),
+    }
+}
+
+/// a day is 24 hours and a month is 30 days, same as postgres' justify_interval
+pub fn convert_to_duration(interval: &PgInterval) -> Duration {
+    let days = interval.months as i64 * 30 + interval.days as i64;
+    let micros = days
+        .saturating_mul(86_400 * 1_000_000)
+        .saturating_add(interval.microseconds);
+    Duration {
+        seconds: micros / 1_000_000,
+        nanos: (micros % 1_000_000 * 1_000) as _,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn convert_to_utc_time_should_reject_out_of_range_timestamps() {
+        let ts = |seconds, nanos| Timestamp { seconds, nanos };
+        assert!(convert_to_utc_time(&ts(MIN_TIMESTAMP_SECONDS, 0)).is_ok());
+        assert!(convert_to_utc_time(&ts(MAX_TIMESTAMP_SECONDS, 999_999_999)).is_ok());
+        for bad in [
+            ts(MIN_TIMESTAMP_SECONDS - 1, 999_999_999),
+            ts(MAX_TIMESTAMP_SECONDS + 1, 0),
+            ts(i64::MIN, 0),
+            ts(0, -1),
+            ts(0, 1_000_000_000),
+        ] {
+            assert_eq!(convert_to_utc_time(&bad), Err(Error::InvalidTimespan));
+        }
+    }
+
+    #[test]
+    fn cmp_timestamp_should_order_by_instant() {
+        let ts = |seconds, nanos| Timestamp { seconds, nanos };
+        assert_eq!(cmp_timestamp(&ts(0, 1), &ts(0, 2)), Ordering::Less);
+        assert_eq!(
+            cmp_timestamp(&ts(1, 0), &ts(0, 999_999_999)),
+            Ordering::Greater
+        );
+        assert_eq!(
+            cmp_timestamp(&ts(-1, 500_000_000), &ts(0, -500_000_000)),
+            Ordering::Equal
+        );
+        assert_eq!(
+            cmp_timestamp(&ts(0, 1_500_000_000), &ts(1, 500_000_000)),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn truncate_to_micros_should_round_down() {
+        let ts = |seconds, nanos| Timestamp { seconds, nanos };
+        assert_eq!(truncate_to_micros(&ts(0, 1_999)), ts(0, 1_000));
+        assert_eq!(truncate_to_micros(&ts(0, -1)), ts(-1, 999_999_000));
+    }
+
+    fn valid_timestamp() -> impl Strategy<Value = Timestamp> {
+        (
+            MIN_TIMESTAMP_SECONDS..=MAX_TIMESTAMP_SECONDS,
+            0..1_000_000_000i32,
+        )
+            .prop_map(|(seconds, nanos)| Timestamp { seconds, nanos })
+    }
+
+    proptest! {
+        #[test]
+        fn valid_timestamps_should_round_trip(ts in valid_timestamp()) {
+            let dt = convert_to_utc_time(&ts).unwrap();
+            prop_assert_eq!(convert_to_timestamp(&dt), ts);
+        }
+
+        #[test]
+        fn cmp_timestamp_should_agree_with_utc_time(a in valid_timestamp(), b in valid_timestamp()) {
+            let (x, y) = (convert_to_utc_time(&a).unwrap(), convert_to_utc_time(&b).unwrap());
+            prop_assert_eq!(cmp_timestamp(&a, &b), x.cmp(&y));
+        }
+
+        #[test]
+        fn any_timestamp_should_convert_or_fail(seconds in any::<i64>(), nanos in any::<i32>()) {
+            let _ = convert_to_utc_time(&Timestamp { seconds, nanos });
+            let _ = cmp_timestamp(&Timestamp { seconds, nanos }, &Timestamp::default());
+        }
+    }
+}
@@ -0,0 +1,4 @@
+DROP TRIGGER waitlist_trigger ON rsvp.reservations;
+DROP FUNCTION rsvp.waitlist_trigger();
+DROP FUNCTION rsvp.promote_waitlist;
+DROP TABLE rsvp.waitlist CASCADE;
@@ -0,0 +1,5 @@
+DROP TRIGGER buffered_timespan_trigger ON rsvp.reservations;
+DROP FUNCTION rsvp.buffered_timespan_trigger();
+ALTER TABLE rsvp.reservations DROP CONSTRAINT reservation_buffer_conflict;
+ALTER TABLE rsvp.reservations DROP COLUMN buffered_timespan;
+DROP TABLE rsvp.resources CASCADE;
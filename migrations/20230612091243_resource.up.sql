@@ -0,0 +1,40 @@
+-- per resource settings, a resource without a row here can still be reserved
+CREATE TABLE rsvp.resources (
+    id VARCHAR(64) NOT NULL,
+    -- setup time before every reservation of the resource
+    pre_buffer INTERVAL NOT NULL DEFAULT '0',
+    -- cleanup time after every reservation of the resource
+    post_buffer INTERVAL NOT NULL DEFAULT '0',
+
+    CONSTRAINT resources_pkey PRIMARY KEY (id),
+    CONSTRAINT resources_buffer_check CHECK (pre_buffer >= '0' AND post_buffer >= '0')
+);
+
+-- timespan expanded by the buffers of the resource, only used by the conflict check
+ALTER TABLE rsvp.reservations ADD COLUMN buffered_timespan TSTZRANGE;
+UPDATE rsvp.reservations SET buffered_timespan = timespan;
+ALTER TABLE rsvp.reservations ALTER COLUMN buffered_timespan SET NOT NULL;
+
+-- added after reservation_conflict, so overlapping windows are still reported by reservation_conflict
+-- and a violation of this one means the reservations only conflict because of the buffers
+ALTER TABLE rsvp.reservations ADD CONSTRAINT reservation_buffer_conflict
+    EXCLUDE USING gist (resource_id WITH =, buffered_timespan WITH &&);
+
+CREATE OR REPLACE FUNCTION rsvp.buffered_timespan_trigger() RETURNS TRIGGER AS $$
+DECLARE
+  _pre interval;
+  _post interval;
+BEGIN
+  SELECT pre_buffer, post_buffer INTO _pre, _post FROM rsvp.resources WHERE id = NEW.resource_id;
+  NEW.buffered_timespan := tstzrange(
+    lower(NEW.timespan) - COALESCE(_pre, '0'),
+    upper(NEW.timespan) + COALESCE(_post, '0'),
+    '[)'
+  );
+  RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER buffered_timespan_trigger
+    BEFORE INSERT OR UPDATE OF resource_id, timespan ON rsvp.reservations
+    FOR EACH ROW EXECUTE PROCEDURE rsvp.buffered_timespan_trigger();
@@ -0,0 +1,3 @@
+ALTER TABLE rsvp.resources DROP COLUMN blackout_dates;
+ALTER TABLE rsvp.resources DROP COLUMN opening_hours;
+ALTER TABLE rsvp.resources DROP COLUMN time_zone;
@@ -0,0 +1,5 @@
+-- opening hours and blackout dates are in the time zone of the resource
+ALTER TABLE rsvp.resources ADD COLUMN time_zone TEXT NOT NULL DEFAULT 'UTC';
+-- [{"weekday": 1, "open": "09:00", "close": "18:00"}, ...], empty means always open
+ALTER TABLE rsvp.resources ADD COLUMN opening_hours JSONB NOT NULL DEFAULT '[]';
+ALTER TABLE rsvp.resources ADD COLUMN blackout_dates DATE[] NOT NULL DEFAULT '{}';
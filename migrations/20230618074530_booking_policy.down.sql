@@ -0,0 +1,8 @@
+ALTER TABLE rsvp.resources DROP COLUMN slot_alignment;
+ALTER TABLE rsvp.resources DROP COLUMN max_advance;
+ALTER TABLE rsvp.resources DROP COLUMN min_lead_time;
+ALTER TABLE rsvp.resources DROP COLUMN max_duration;
+ALTER TABLE rsvp.resources DROP COLUMN min_duration;
+ALTER TABLE rsvp.resources DROP COLUMN resource_type;
+
+DROP TABLE rsvp.resource_types;
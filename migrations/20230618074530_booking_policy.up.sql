@@ -0,0 +1,18 @@
+-- a NULL rule is not checked, rules of a resource take precedence over the ones of its type
+CREATE TABLE rsvp.resource_types (
+    id VARCHAR(64) NOT NULL,
+    min_duration INTERVAL,
+    max_duration INTERVAL,
+    min_lead_time INTERVAL,
+    max_advance INTERVAL,
+    slot_alignment INTERVAL,
+
+    CONSTRAINT resource_types_pkey PRIMARY KEY (id)
+);
+
+ALTER TABLE rsvp.resources ADD COLUMN resource_type VARCHAR(64) NOT NULL DEFAULT '';
+ALTER TABLE rsvp.resources ADD COLUMN min_duration INTERVAL;
+ALTER TABLE rsvp.resources ADD COLUMN max_duration INTERVAL;
+ALTER TABLE rsvp.resources ADD COLUMN min_lead_time INTERVAL;
+ALTER TABLE rsvp.resources ADD COLUMN max_advance INTERVAL;
+ALTER TABLE rsvp.resources ADD COLUMN slot_alignment INTERVAL;
@@ -0,0 +1,5 @@
+DROP INDEX rsvp.resources_resource_type_idx;
+
+ALTER TABLE rsvp.resource_types DROP COLUMN max_weekly_duration;
+ALTER TABLE rsvp.resource_types DROP COLUMN max_pending;
+ALTER TABLE rsvp.resource_types DROP COLUMN max_active;
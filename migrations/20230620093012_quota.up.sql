@@ -0,0 +1,6 @@
+-- per user limits over all resources of the type, 0 or NULL means unlimited
+ALTER TABLE rsvp.resource_types ADD COLUMN max_active INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE rsvp.resource_types ADD COLUMN max_pending INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE rsvp.resource_types ADD COLUMN max_weekly_duration INTERVAL;
+
+CREATE INDEX resources_resource_type_idx ON rsvp.resources (resource_type);
@@ -0,0 +1,34 @@
+CREATE OR REPLACE FUNCTION rsvp.reservations_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        -- update reservation_changes table
+        INSERT INTO rsvp.reservation_changes (reservation_id, op) VALUES (NEW.id, 'create');
+    ELSIF TG_OP = 'UPDATE' THEN
+        -- if status is changed, update reservation_changes table
+        IF OLD.status <> NEW.status THEN
+            INSERT INTO rsvp.reservation_changes (reservation_id, op) VALUES (NEW.id, 'update');
+        END IF;
+    ELSIF TG_OP = 'DELETE' THEN
+        -- if status is deleted, update reservation_changes table
+        INSERT INTO rsvp.reservation_changes (reservation_id, op) VALUES (OLD.id, 'delete');
+    END IF;
+    -- notify a channel called reservation_update
+    NOTIFY reservation_update;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+ALTER TABLE rsvp.resources DROP COLUMN approvers;
+ALTER TABLE rsvp.resources DROP COLUMN requires_approval;
+
+ALTER TABLE rsvp.reservation_changes DROP COLUMN reason;
+ALTER TABLE rsvp.reservation_changes DROP COLUMN actor;
+
+-- enum values can't be dropped, recreate the type without them
+UPDATE rsvp.reservation_changes SET op = 'update' WHERE op = 'approve';
+UPDATE rsvp.reservation_changes SET op = 'delete' WHERE op = 'reject';
+ALTER TYPE rsvp.reservation_update_type RENAME TO reservation_update_type_old;
+CREATE TYPE rsvp.reservation_update_type AS ENUM ('unknown', 'create', 'update', 'delete');
+ALTER TABLE rsvp.reservation_changes
+    ALTER COLUMN op TYPE rsvp.reservation_update_type USING op::text::rsvp.reservation_update_type;
+DROP TYPE rsvp.reservation_update_type_old;
@@ -0,0 +1,40 @@
+ALTER TYPE rsvp.reservation_update_type ADD VALUE 'approve';
+ALTER TYPE rsvp.reservation_update_type ADD VALUE 'reject';
+
+-- who made the change and why, set for decisions such as approve and reject
+ALTER TABLE rsvp.reservation_changes ADD COLUMN actor TEXT;
+ALTER TABLE rsvp.reservation_changes ADD COLUMN reason TEXT;
+
+-- reservations of these resources stay pending until one of the approvers approves them
+ALTER TABLE rsvp.resources ADD COLUMN requires_approval BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE rsvp.resources ADD COLUMN approvers TEXT[] NOT NULL DEFAULT '{}';
+
+-- a transaction can describe its next change with the local settings rsvp.op, rsvp.actor and rsvp.reason,
+-- they are used for one change only, so changes made by other triggers (e.g. waitlist) are not mislabeled
+CREATE OR REPLACE FUNCTION rsvp.reservations_trigger() RETURNS TRIGGER AS $$
+DECLARE
+    _op rsvp.reservation_update_type := NULLIF(current_setting('rsvp.op', true), '');
+    _actor text := NULLIF(current_setting('rsvp.actor', true), '');
+    _reason text := NULLIF(current_setting('rsvp.reason', true), '');
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        -- update reservation_changes table
+        INSERT INTO rsvp.reservation_changes (reservation_id, op, actor, reason)
+            VALUES (NEW.id, COALESCE(_op, 'create'), _actor, _reason);
+    ELSIF TG_OP = 'UPDATE' THEN
+        -- if status is changed, update reservation_changes table
+        IF OLD.status <> NEW.status THEN
+            INSERT INTO rsvp.reservation_changes (reservation_id, op, actor, reason)
+                VALUES (NEW.id, COALESCE(_op, 'update'), _actor, _reason);
+        END IF;
+    ELSIF TG_OP = 'DELETE' THEN
+        -- if status is deleted, update reservation_changes table
+        INSERT INTO rsvp.reservation_changes (reservation_id, op, actor, reason)
+            VALUES (OLD.id, COALESCE(_op, 'delete'), _actor, _reason);
+    END IF;
+    PERFORM set_config('rsvp.op', '', true), set_config('rsvp.actor', '', true), set_config('rsvp.reason', '', true);
+    -- notify a channel called reservation_update
+    NOTIFY reservation_update;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
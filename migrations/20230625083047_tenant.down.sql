@@ -0,0 +1,101 @@
+CREATE OR REPLACE FUNCTION rsvp.waitlist_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        PERFORM rsvp.promote_waitlist(OLD.resource_id, OLD.timespan);
+    ELSIF OLD.resource_id <> NEW.resource_id OR OLD.timespan <> NEW.timespan THEN
+        PERFORM rsvp.promote_waitlist(OLD.resource_id, OLD.timespan);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP FUNCTION rsvp.promote_waitlist(text, text, tstzrange);
+
+CREATE OR REPLACE FUNCTION rsvp.promote_waitlist(rid text, during tstzrange) RETURNS void AS $$
+DECLARE
+  _entry rsvp.waitlist;
+BEGIN
+  FOR _entry IN
+    SELECT * FROM rsvp.waitlist WHERE resource_id = rid AND timespan && during ORDER BY id FOR UPDATE
+  LOOP
+    BEGIN
+      -- the reservations trigger records the create change for the new reservation
+      INSERT INTO rsvp.reservations (user_id, resource_id, timespan, note, status)
+        VALUES (_entry.user_id, _entry.resource_id, _entry.timespan, _entry.note, 'pending');
+      DELETE FROM rsvp.waitlist WHERE id = _entry.id;
+    EXCEPTION WHEN exclusion_violation THEN
+      -- still taken, keep waiting
+    END;
+  END LOOP;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION rsvp.reservations_trigger() RETURNS TRIGGER AS $$
+DECLARE
+    _op rsvp.reservation_update_type := NULLIF(current_setting('rsvp.op', true), '');
+    _actor text := NULLIF(current_setting('rsvp.actor', true), '');
+    _reason text := NULLIF(current_setting('rsvp.reason', true), '');
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        -- update reservation_changes table
+        INSERT INTO rsvp.reservation_changes (reservation_id, op, actor, reason)
+            VALUES (NEW.id, COALESCE(_op, 'create'), _actor, _reason);
+    ELSIF TG_OP = 'UPDATE' THEN
+        -- if status is changed, update reservation_changes table
+        IF OLD.status <> NEW.status THEN
+            INSERT INTO rsvp.reservation_changes (reservation_id, op, actor, reason)
+                VALUES (NEW.id, COALESCE(_op, 'update'), _actor, _reason);
+        END IF;
+    ELSIF TG_OP = 'DELETE' THEN
+        -- if status is deleted, update reservation_changes table
+        INSERT INTO rsvp.reservation_changes (reservation_id, op, actor, reason)
+            VALUES (OLD.id, COALESCE(_op, 'delete'), _actor, _reason);
+    END IF;
+    PERFORM set_config('rsvp.op', '', true), set_config('rsvp.actor', '', true), set_config('rsvp.reason', '', true);
+    -- notify a channel called reservation_update
+    NOTIFY reservation_update;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION rsvp.buffered_timespan_trigger() RETURNS TRIGGER AS $$
+DECLARE
+  _pre interval;
+  _post interval;
+BEGIN
+  SELECT pre_buffer, post_buffer INTO _pre, _post FROM rsvp.resources WHERE id = NEW.resource_id;
+  NEW.buffered_timespan := tstzrange(
+    lower(NEW.timespan) - COALESCE(_pre, '0'),
+    upper(NEW.timespan) + COALESCE(_post, '0'),
+    '[)'
+  );
+  RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP INDEX rsvp.waitlist_resource_timespan_idx;
+CREATE INDEX waitlist_resource_timespan_idx ON rsvp.waitlist USING gist (resource_id, timespan);
+DROP INDEX rsvp.reservations_resource_id_idx;
+DROP INDEX rsvp.reservations_user_id_idx;
+CREATE INDEX reservations_user_id_idx ON rsvp.reservations (user_id);
+CREATE INDEX reservations_resource_id_idx ON rsvp.reservations (resource_id);
+
+ALTER TABLE rsvp.reservations DROP CONSTRAINT reservation_buffer_conflict;
+ALTER TABLE rsvp.reservations DROP CONSTRAINT reservation_conflict;
+ALTER TABLE rsvp.reservations ADD CONSTRAINT reservation_conflict
+    EXCLUDE USING gist (resource_id WITH =, timespan WITH &&);
+ALTER TABLE rsvp.reservations ADD CONSTRAINT reservation_buffer_conflict
+    EXCLUDE USING gist (resource_id WITH =, buffered_timespan WITH &&);
+
+DROP INDEX rsvp.resources_resource_type_idx;
+CREATE INDEX resources_resource_type_idx ON rsvp.resources (resource_type);
+ALTER TABLE rsvp.resource_types DROP CONSTRAINT resource_types_pkey;
+ALTER TABLE rsvp.resource_types ADD CONSTRAINT resource_types_pkey PRIMARY KEY (id);
+ALTER TABLE rsvp.resources DROP CONSTRAINT resources_pkey;
+ALTER TABLE rsvp.resources ADD CONSTRAINT resources_pkey PRIMARY KEY (id);
+
+ALTER TABLE rsvp.resource_types DROP COLUMN tenant_id;
+ALTER TABLE rsvp.resources DROP COLUMN tenant_id;
+ALTER TABLE rsvp.waitlist DROP COLUMN tenant_id;
+ALTER TABLE rsvp.reservation_changes DROP COLUMN tenant_id;
+ALTER TABLE rsvp.reservations DROP COLUMN tenant_id;
@@ -0,0 +1,2 @@
+DROP INDEX rsvp.reservations_metadata_idx;
+ALTER TABLE rsvp.reservations DROP COLUMN metadata;
@@ -0,0 +1,3 @@
+-- structured labels of a reservation, matched with @> by queries and filters
+ALTER TABLE rsvp.reservations ADD COLUMN metadata jsonb NOT NULL DEFAULT '{}';
+CREATE INDEX reservations_metadata_idx ON rsvp.reservations USING gin (metadata jsonb_path_ops);
@@ -0,0 +1,7 @@
+DROP TRIGGER version_trigger ON rsvp.reservations;
+DROP FUNCTION rsvp.version_trigger();
+
+ALTER TABLE rsvp.reservations
+    DROP COLUMN version,
+    DROP COLUMN updated_at,
+    DROP COLUMN created_at;
@@ -0,0 +1,25 @@
+ALTER TABLE rsvp.reservations
+    ADD COLUMN created_at timestamptz NOT NULL DEFAULT now(),
+    ADD COLUMN updated_at timestamptz NOT NULL DEFAULT now(),
+    ADD COLUMN version bigint NOT NULL DEFAULT 1;
+
+-- every visible change moves the reservation to a new version,
+-- recomputing the buffered timespan alone doesn't count as a change
+CREATE OR REPLACE FUNCTION rsvp.version_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    IF (OLD.user_id, OLD.resource_id, OLD.timespan, OLD.note, OLD.status, OLD.metadata)
+        IS DISTINCT FROM (NEW.user_id, NEW.resource_id, NEW.timespan, NEW.note, NEW.status, NEW.metadata) THEN
+        NEW.version := OLD.version + 1;
+        NEW.updated_at := now();
+    ELSE
+        NEW.version := OLD.version;
+        NEW.updated_at := OLD.updated_at;
+    END IF;
+    NEW.created_at := OLD.created_at;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER version_trigger
+    BEFORE UPDATE ON rsvp.reservations
+    FOR EACH ROW EXECUTE PROCEDURE rsvp.version_trigger();
@@ -0,0 +1,13 @@
+-- responses of mutating requests sent with an idempotency key, so a retry gets the same answer
+-- response is null while the first attempt is still running
+CREATE TABLE rsvp.idempotency_keys (
+    tenant_id VARCHAR(64) NOT NULL,
+    key VARCHAR(255) NOT NULL,
+    method VARCHAR(64) NOT NULL,
+    request BYTEA NOT NULL,
+    response BYTEA,
+    expires_at TIMESTAMPTZ NOT NULL,
+
+    CONSTRAINT idempotency_keys_pkey PRIMARY KEY (tenant_id, key)
+);
+CREATE INDEX idempotency_keys_expires_at_idx ON rsvp.idempotency_keys (expires_at);
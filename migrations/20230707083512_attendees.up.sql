@@ -0,0 +1,19 @@
+-- people taking part in a reservation, found with @> by their user id
+ALTER TABLE rsvp.reservations ADD COLUMN attendees jsonb NOT NULL DEFAULT '[]';
+CREATE INDEX reservations_attendees_idx ON rsvp.reservations USING gin (attendees jsonb_path_ops);
+
+-- a change of the attendees is a new version too
+CREATE OR REPLACE FUNCTION rsvp.version_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    IF (OLD.user_id, OLD.resource_id, OLD.timespan, OLD.note, OLD.status, OLD.metadata, OLD.attendees)
+        IS DISTINCT FROM (NEW.user_id, NEW.resource_id, NEW.timespan, NEW.note, NEW.status, NEW.metadata, NEW.attendees) THEN
+        NEW.version := OLD.version + 1;
+        NEW.updated_at := now();
+    ELSE
+        NEW.version := OLD.version;
+        NEW.updated_at := OLD.updated_at;
+    END IF;
+    NEW.created_at := OLD.created_at;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,5 @@
+ALTER TABLE rsvp.reservations DROP CONSTRAINT reservation_user_conflict;
+DROP TRIGGER user_exclusive_trigger ON rsvp.reservations;
+DROP FUNCTION rsvp.user_exclusive_trigger();
+ALTER TABLE rsvp.reservations DROP COLUMN user_exclusive;
+ALTER TABLE rsvp.resource_types DROP COLUMN user_exclusive;
@@ -0,0 +1,22 @@
+-- a user can hold only one reservation at a time over all resources of a user exclusive type
+ALTER TABLE rsvp.resource_types ADD COLUMN user_exclusive BOOLEAN NOT NULL DEFAULT false;
+
+-- copied from the resource type, so the exclusion constraint can use it
+ALTER TABLE rsvp.reservations ADD COLUMN user_exclusive BOOLEAN NOT NULL DEFAULT false;
+
+CREATE OR REPLACE FUNCTION rsvp.user_exclusive_trigger() RETURNS TRIGGER AS $$
+BEGIN
+  SELECT t.user_exclusive INTO NEW.user_exclusive FROM rsvp.resources s
+    JOIN rsvp.resource_types t ON t.tenant_id = s.tenant_id AND t.id = s.resource_type
+    WHERE s.tenant_id = NEW.tenant_id AND s.id = NEW.resource_id;
+  NEW.user_exclusive := COALESCE(NEW.user_exclusive, false);
+  RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER user_exclusive_trigger
+    BEFORE INSERT OR UPDATE OF resource_id, timespan ON rsvp.reservations
+    FOR EACH ROW EXECUTE PROCEDURE rsvp.user_exclusive_trigger();
+
+ALTER TABLE rsvp.reservations ADD CONSTRAINT reservation_user_conflict
+    EXCLUDE USING gist (tenant_id WITH =, user_id WITH =, timespan WITH &&) WHERE (user_exclusive);
@@ -0,0 +1,18 @@
+CREATE OR REPLACE FUNCTION rsvp.version_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    IF (OLD.user_id, OLD.resource_id, OLD.timespan, OLD.note, OLD.status, OLD.metadata, OLD.attendees)
+        IS DISTINCT FROM (NEW.user_id, NEW.resource_id, NEW.timespan, NEW.note, NEW.status, NEW.metadata, NEW.attendees) THEN
+        NEW.version := OLD.version + 1;
+        NEW.updated_at := now();
+    ELSE
+        NEW.version := OLD.version;
+        NEW.updated_at := OLD.updated_at;
+    END IF;
+    NEW.created_at := OLD.created_at;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER time_zone_trigger ON rsvp.reservations;
+DROP FUNCTION rsvp.time_zone_trigger();
+ALTER TABLE rsvp.reservations DROP COLUMN time_zone;
@@ -0,0 +1,36 @@
+-- the IANA time zone a reservation was made in, its wall clock times are kept in this zone
+ALTER TABLE rsvp.reservations ADD COLUMN time_zone TEXT NOT NULL DEFAULT '';
+UPDATE rsvp.reservations r SET time_zone = COALESCE(
+    (SELECT s.time_zone FROM rsvp.resources s WHERE s.tenant_id = r.tenant_id AND s.id = r.resource_id), 'UTC');
+
+-- a reservation made without a time zone gets the one of its resource
+CREATE OR REPLACE FUNCTION rsvp.time_zone_trigger() RETURNS TRIGGER AS $$
+BEGIN
+  IF NEW.time_zone = '' THEN
+    SELECT s.time_zone INTO NEW.time_zone FROM rsvp.resources s
+      WHERE s.tenant_id = NEW.tenant_id AND s.id = NEW.resource_id;
+    NEW.time_zone := COALESCE(NEW.time_zone, 'UTC');
+  END IF;
+  RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER time_zone_trigger
+    BEFORE INSERT OR UPDATE OF time_zone ON rsvp.reservations
+    FOR EACH ROW EXECUTE PROCEDURE rsvp.time_zone_trigger();
+
+-- a change of the time zone is a new version too
+CREATE OR REPLACE FUNCTION rsvp.version_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    IF (OLD.user_id, OLD.resource_id, OLD.timespan, OLD.note, OLD.status, OLD.metadata, OLD.attendees, OLD.time_zone)
+        IS DISTINCT FROM (NEW.user_id, NEW.resource_id, NEW.timespan, NEW.note, NEW.status, NEW.metadata, NEW.attendees, NEW.time_zone) THEN
+        NEW.version := OLD.version + 1;
+        NEW.updated_at := now();
+    ELSE
+        NEW.version := OLD.version;
+        NEW.updated_at := OLD.updated_at;
+    END IF;
+    NEW.created_at := OLD.created_at;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,6 @@
+-- check in and check out dates of a whole day reservation, in the time zone of the resource.
+-- timespan holds the same days from local midnight to midnight, so conflicts with timed
+-- reservations are found by the existing constraints
+ALTER TABLE rsvp.reservations ADD COLUMN dates daterange;
+ALTER TABLE rsvp.reservations ADD CONSTRAINT reservation_dates_bounded
+    CHECK (dates IS NULL OR (NOT isempty(dates) AND NOT lower_inf(dates) AND NOT upper_inf(dates)));
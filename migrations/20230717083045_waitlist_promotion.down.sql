@@ -0,0 +1,37 @@
+-- turn waitlisted requests overlapping the freed window into pending reservations
+-- entries are tried first come first served, an entry which still conflicts keeps waiting
+CREATE OR REPLACE FUNCTION rsvp.promote_waitlist(tid text, rid text, during tstzrange) RETURNS void AS $$
+DECLARE
+  _entry rsvp.waitlist;
+BEGIN
+  FOR _entry IN
+    SELECT * FROM rsvp.waitlist WHERE tenant_id = tid AND resource_id = rid AND timespan && during
+      ORDER BY id FOR UPDATE
+  LOOP
+    BEGIN
+      -- the reservations trigger records the create change for the new reservation
+      INSERT INTO rsvp.reservations (tenant_id, user_id, resource_id, timespan, note, status)
+        VALUES (_entry.tenant_id, _entry.user_id, _entry.resource_id, _entry.timespan, _entry.note, 'pending');
+      DELETE FROM rsvp.waitlist WHERE id = _entry.id;
+    EXCEPTION WHEN exclusion_violation THEN
+      -- still taken, keep waiting
+    END;
+  END LOOP;
+END;
+$$ LANGUAGE plpgsql;
+
+-- when a reservation is cancelled or moved away, its old window may fit a waitlisted request
+CREATE OR REPLACE FUNCTION rsvp.waitlist_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        PERFORM rsvp.promote_waitlist(OLD.tenant_id, OLD.resource_id, OLD.timespan);
+    ELSIF OLD.resource_id <> NEW.resource_id OR OLD.timespan <> NEW.timespan THEN
+        PERFORM rsvp.promote_waitlist(OLD.tenant_id, OLD.resource_id, OLD.timespan);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER waitlist_trigger
+    AFTER UPDATE OR DELETE ON rsvp.reservations
+    FOR EACH ROW EXECUTE PROCEDURE rsvp.waitlist_trigger();
@@ -0,0 +1,5 @@
+-- waitlisted requests are promoted by the reservation manager, which runs them through the same
+-- opening hours, policy, quota and approval checks as any other reservation
+DROP TRIGGER waitlist_trigger ON rsvp.reservations;
+DROP FUNCTION rsvp.waitlist_trigger();
+DROP FUNCTION rsvp.promote_waitlist(text, text, tstzrange);
@@ -0,0 +1,2 @@
+ALTER TABLE rsvp.waitlist DROP COLUMN dates;
+ALTER TABLE rsvp.waitlist DROP COLUMN time_zone;
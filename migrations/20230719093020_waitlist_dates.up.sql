@@ -0,0 +1,4 @@
+-- a waitlisted request keeps its time zone and the dates of a whole day reservation,
+-- so it is promoted to the same kind of reservation it was made as
+ALTER TABLE rsvp.waitlist ADD COLUMN time_zone TEXT NOT NULL DEFAULT '';
+ALTER TABLE rsvp.waitlist ADD COLUMN dates daterange;
@@ -1,28 +1,100 @@
+mod idempotency;
 mod manager;
+mod policy;
+mod quota;
+mod schedule;
 
-use abi::Error;
+use abi::{Error, TenantId};
 use async_trait::async_trait;
-use sqlx::PgPool;
-use tokio::sync::mpsc;
+use prost_types::{FieldMask, Timestamp};
+use sqlx::{PgPool, Postgres, Transaction};
+use std::sync::Arc;
+use tokio::sync::{mpsc, Mutex};
 
 pub struct ReservationManager {
     pub pool: PgPool,
+    /// every operation only sees and changes the data of this tenant
+    pub tenant: TenantId,
+    /// when set, changes are made in this transaction instead of their own, see `idempotent`
+    tx: Option<SharedTransaction>,
 }
 
+type SharedTransaction = Arc<Mutex<Transaction<'static, Postgres>>>;
+
 #[async_trait]
 pub trait Rsvp {
     /// make a reservation
     async fn reserve(&self, rsvp: abi::Reservation) -> Result<abi::Reservation, Error>;
-    /// change reservation status
-    async fn change_status(&self, id: abi::ReservationId) -> Result<abi::Reservation, Error>;
-    /// update note
-    async fn update_note(
+    /// run every check of reserve without keeping the reservation, returns it as it would be
+    /// stored, without an id
+    async fn check(&self, rsvp: abi::Reservation) -> Result<abi::Reservation, Error>;
+    /// make several reservations atomically, either all of them or none
+    async fn reserve_many(
+        &self,
+        rsvps: Vec<abi::Reservation>,
+    ) -> Result<Vec<abi::Reservation>, Error>;
+    /// change reservation status, only if it is still at expected_version when one is given
+    async fn change_status(
+        &self,
+        id: abi::ReservationId,
+        expected_version: Option<i64>,
+    ) -> Result<abi::Reservation, Error>;
+    /// confirm a pending reservation of a resource requiring approval
+    async fn approve(
+        &self,
+        id: abi::ReservationId,
+        approver: abi::UserId,
+    ) -> Result<abi::Reservation, Error>;
+    /// remove a pending reservation of a resource requiring approval, keeping the reason
+    async fn reject(
+        &self,
+        id: abi::ReservationId,
+        approver: abi::UserId,
+        reason: String,
+    ) -> Result<abi::Reservation, Error>;
+    /// update the fields listed in mask with the values from rsvp,
+    /// only if it is still at expected_version when one is given
+    async fn update(
+        &self,
+        id: abi::ReservationId,
+        rsvp: abi::Reservation,
+        mask: FieldMask,
+        expected_version: Option<i64>,
+    ) -> Result<abi::Reservation, Error>;
+    /// add or remove attendees as organizer, or answer an invitation as attendee
+    async fn update_attendees(
+        &self,
+        id: abi::ReservationId,
+        caller: abi::UserId,
+        add: Vec<abi::Attendee>,
+        remove: Vec<abi::UserId>,
+        expected_version: Option<i64>,
+    ) -> Result<abi::Reservation, Error>;
+    /// move reservation to a new window, optionally on another resource
+    async fn reschedule(
+        &self,
+        id: abi::ReservationId,
+        start: Timestamp,
+        end: Timestamp,
+        resource_id: Option<abi::ResourceId>,
+    ) -> Result<abi::Reservation, Error>;
+    /// delete reservation, only if it is still at expected_version when one is given
+    async fn delete(
         &self,
         id: abi::ReservationId,
-        note: String,
+        expected_version: Option<i64>,
     ) -> Result<abi::Reservation, Error>;
-    /// delete reservation
-    async fn delete(&self, id: abi::ReservationId) -> Result<abi::Reservation, Error>;
+    /// confirm every pending reservation selected by the request
+    async fn confirm_many(&self, req: abi::BulkRequest) -> Result<Vec<abi::BulkResult>, Error>;
+    /// delete every reservation selected by the request
+    async fn delete_many(&self, req: abi::BulkRequest) -> Result<Vec<abi::BulkResult>, Error>;
+    /// wait for a taken window, reserve it right away if it is free
+    async fn join_waitlist(
+        &self,
+        rsvp: abi::Reservation,
+    ) -> Result<abi::JoinWaitlistResponse, Error>;
+    /// remove a waitlist entry
+    async fn leave_waitlist(&self, id: i64) -> Result<abi::WaitlistEntry, Error>;
     /// get reservation by id
     async fn get(&self, id: abi::ReservationId) -> Result<abi::Reservation, Error>;
     /// query reservations
@@ -35,4 +107,29 @@ pub trait Rsvp {
         &self,
         filter: abi::ReservationFilter,
     ) -> Result<(abi::FilterPager, Vec<abi::Reservation>), Error>;
+    /// create or change the settings of a resource
+    async fn set_resource(&self, resource: abi::Resource) -> Result<abi::Resource, Error>;
+    /// get the settings of a resource
+    async fn get_resource(&self, id: abi::ResourceId) -> Result<abi::Resource, Error>;
+    /// create or change the booking policy of a resource type
+    async fn set_resource_type(
+        &self,
+        resource_type: abi::ResourceType,
+    ) -> Result<abi::ResourceType, Error>;
+    /// get a resource type
+    async fn get_resource_type(&self, id: String) -> Result<abi::ResourceType, Error>;
+    /// free windows of a resource within [start, end), honoring opening hours and buffers
+    async fn availability(
+        &self,
+        resource_id: abi::ResourceId,
+        start: Timestamp,
+        end: Timestamp,
+    ) -> Result<Vec<abi::TimeWindow>, Error>;
+    /// up to max free alternatives for a reservation, nearest first: the same window on
+    /// resources of the same type and windows of the same length on its own resource
+    async fn suggest(
+        &self,
+        rsvp: abi::Reservation,
+        max: usize,
+    ) -> Result<Vec<abi::ReservationWindow>, Error>;
 }
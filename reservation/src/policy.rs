@@ -0,0 +1,179 @@
+//! booking policy of a resource, checked for every new or moved reservation
+
+use abi::{BookingPolicy, Error, PolicyRule};
+use chrono::{DateTime, Duration, Utc};
+use chrono_tz::Tz;
+
+/// check [start, end) against every rule set in the policy, now is when the reservation is made
+pub fn check(
+    policy: &BookingPolicy,
+    tz: &Tz,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<(), Error> {
+    let rule = |d: &Option<prost_types::Duration>| d.as_ref().map(to_duration);
+
+    if let Some(min) = rule(&policy.min_duration) {
+        if end - start < min {
+            return Err(Error::PolicyViolation(PolicyRule::MinDuration));
+        }
+    }
+    if let Some(max) = rule(&policy.max_duration) {
+        if end - start > max {
+            return Err(Error::PolicyViolation(PolicyRule::MaxDuration));
+        }
+    }
+    if let Some(lead) = rule(&policy.min_lead_time) {
+        if start - now < lead {
+            return Err(Error::PolicyViolation(PolicyRule::MinLeadTime));
+        }
+    }
+    if let Some(advance) = rule(&policy.max_advance) {
+        if start - now > advance {
+            return Err(Error::PolicyViolation(PolicyRule::MaxAdvance));
+        }
+    }
+    if let Some(alignment) = rule(&policy.slot_alignment) {
+        // align on the wall clock of the resource, so :00/:30 holds in any time zone
+        let local = start.with_timezone(tz).naive_local().and_utc();
+        let alignment = alignment.num_microseconds().unwrap_or(i64::MAX);
+        if alignment > 0 && local.timestamp_micros().rem_euclid(alignment) != 0 {
+            return Err(Error::PolicyViolation(PolicyRule::SlotAlignment));
+        }
+    }
+
+    Ok(())
+}
+
+fn to_duration(d: &prost_types::Duration) -> Duration {
+    Duration::seconds(d.seconds) + Duration::nanoseconds(d.nanos as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn minutes(m: i64) -> Option<prost_types::Duration> {
+        Some(prost_types::Duration {
+            seconds: m * 60,
+            nanos: 0,
+        })
+    }
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    fn check_at(policy: &BookingPolicy, tz: Tz, start: &str, end: &str) -> Result<(), Error> {
+        check(
+            policy,
+            &tz,
+            utc(start),
+            utc(end),
+            utc("2023-01-01T00:00:00Z"),
+        )
+    }
+
+    #[test]
+    fn duration_rules_should_work() {
+        let policy = BookingPolicy {
+            min_duration: minutes(30),
+            max_duration: minutes(120),
+            ..Default::default()
+        };
+        assert!(check_at(
+            &policy,
+            Tz::UTC,
+            "2023-01-02T10:00:00Z",
+            "2023-01-02T10:30:00Z"
+        )
+        .is_ok());
+        assert_eq!(
+            check_at(
+                &policy,
+                Tz::UTC,
+                "2023-01-02T10:00:00Z",
+                "2023-01-02T10:20:00Z"
+            )
+            .unwrap_err(),
+            Error::PolicyViolation(PolicyRule::MinDuration)
+        );
+        assert_eq!(
+            check_at(
+                &policy,
+                Tz::UTC,
+                "2023-01-02T10:00:00Z",
+                "2023-01-02T12:01:00Z"
+            )
+            .unwrap_err(),
+            Error::PolicyViolation(PolicyRule::MaxDuration)
+        );
+    }
+
+    #[test]
+    fn advance_rules_should_work() {
+        let policy = BookingPolicy {
+            min_lead_time: minutes(60),
+            max_advance: minutes(7 * 24 * 60),
+            ..Default::default()
+        };
+        assert!(check_at(
+            &policy,
+            Tz::UTC,
+            "2023-01-01T01:00:00Z",
+            "2023-01-01T02:00:00Z"
+        )
+        .is_ok());
+        assert_eq!(
+            check_at(
+                &policy,
+                Tz::UTC,
+                "2023-01-01T00:30:00Z",
+                "2023-01-01T02:00:00Z"
+            )
+            .unwrap_err(),
+            Error::PolicyViolation(PolicyRule::MinLeadTime)
+        );
+        assert_eq!(
+            check_at(
+                &policy,
+                Tz::UTC,
+                "2023-01-09T00:00:00Z",
+                "2023-01-09T02:00:00Z"
+            )
+            .unwrap_err(),
+            Error::PolicyViolation(PolicyRule::MaxAdvance)
+        );
+    }
+
+    #[test]
+    fn slot_alignment_should_use_local_time() {
+        let policy = BookingPolicy {
+            slot_alignment: minutes(30),
+            ..Default::default()
+        };
+        assert!(check_at(
+            &policy,
+            Tz::UTC,
+            "2023-01-02T10:30:00Z",
+            "2023-01-02T11:00:00Z"
+        )
+        .is_ok());
+        assert_eq!(
+            check_at(
+                &policy,
+                Tz::UTC,
+                "2023-01-02T10:15:00Z",
+                "2023-01-02T11:00:00Z"
+            )
+            .unwrap_err(),
+            Error::PolicyViolation(PolicyRule::SlotAlignment)
+        );
+
+        // 10:15 utc is 16:00 in Kathmandu (+05:45)
+        let tz: Tz = "Asia/Kathmandu".parse().unwrap();
+        assert!(check_at(&policy, tz, "2023-01-02T10:15:00Z", "2023-01-02T11:15:00Z").is_ok());
+        assert!(check_at(&policy, tz, "2023-01-02T10:30:00Z", "2023-01-02T11:30:00Z").is_err());
+    }
+}
@@ -0,0 +1,174 @@
+//! per user quotas over all resources of a type
+
+use abi::{
+    convert_to_utc_time, Error, Quota, QuotaKind, QuotaUsage, Reservation, ReservationStatus,
+};
+use chrono::{DateTime, Datelike, Duration, Utc};
+use chrono_tz::Tz;
+use sqlx::PgConnection;
+
+use crate::schedule::{start_of_day, Window};
+
+/// check the quota of the resource type before the reservation is inserted in the same transaction
+///
+/// the reservations of the user are locked first, so concurrent requests can't both squeeze in.
+/// an existing reservation being edited doesn't count against its own new window.
+/// the weekly duration is checked for every week in tz the reservation touches
+pub async fn check(
+    conn: &mut PgConnection,
+    tenant: &str,
+    quota: &Quota,
+    resource_type: &str,
+    tz: &Tz,
+    rsvp: &Reservation,
+) -> Result<(), Error> {
+    if quota == &Quota::default() {
+        return Ok(());
+    }
+
+    sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1 || '/' || $2))")
+        .bind(tenant)
+        .bind(&rsvp.user_id)
+        .execute(&mut *conn)
+        .await?;
+
+    let sql = "SELECT count(*) AS active, count(*) FILTER (WHERE r.status = 'pending') AS pending
+        FROM rsvp.reservations r JOIN rsvp.resources s ON r.tenant_id = s.tenant_id AND r.resource_id = s.id
+        WHERE r.tenant_id = $1 AND r.user_id = $2 AND s.resource_type = $3 AND r.status IN ('pending', 'confirmed')
+            AND upper(r.timespan) > now() AND r.id <> $4";
+    let (active, pending): (i64, i64) = sqlx::query_as(sql)
+        .bind(tenant)
+        .bind(&rsvp.user_id)
+        .bind(resource_type)
+        .bind(rsvp.id)
+        .fetch_one(&mut *conn)
+        .await?;
+    exceed(QuotaKind::MaxActive, active, 1, quota.max_active as _)?;
+    let holds = (rsvp.status != ReservationStatus::Confirmed as i32) as i64;
+    exceed(
+        QuotaKind::MaxPending,
+        pending,
+        holds,
+        quota.max_pending as _,
+    )?;
+
+    if let Some(limit) = &quota.max_weekly_duration {
+        let start = convert_to_utc_time(rsvp.start.as_ref().unwrap())?;
+        let end = convert_to_utc_time(rsvp.end.as_ref().unwrap())?;
+        let weeks = weeks_of(tz, start, end);
+        let (starts, ends): (Vec<_>, Vec<_>) = weeks.iter().cloned().unzip();
+
+        // the usage of every week the reservation touches, in the order of weeks
+        let sql = "WITH weeks AS (
+                SELECT tstzrange(s, e) AS week, n FROM unnest($4::timestamptz[], $5::timestamptz[]) WITH ORDINALITY AS w(s, e, n)
+            )
+            SELECT COALESCE(EXTRACT(EPOCH FROM sum(upper(r.timespan * w.week) - lower(r.timespan * w.week))), 0)::bigint
+            FROM weeks w
+            LEFT JOIN (rsvp.reservations r JOIN rsvp.resources s ON r.tenant_id = s.tenant_id AND r.resource_id = s.id)
+                ON r.tenant_id = $1 AND r.user_id = $2 AND s.resource_type = $3 AND r.status IN ('pending', 'confirmed')
+                AND r.timespan && w.week AND r.id <> $6
+            GROUP BY w.n ORDER BY w.n";
+        let used: Vec<i64> = sqlx::query_scalar(sql)
+            .bind(tenant)
+            .bind(&rsvp.user_id)
+            .bind(resource_type)
+            .bind(&starts)
+            .bind(&ends)
+            .bind(rsvp.id)
+            .fetch_all(&mut *conn)
+            .await?;
+
+        for ((week_start, week_end), used) in weeks.into_iter().zip(used) {
+            let requested = (end.min(week_end) - start.max(week_start)).num_seconds();
+            exceed(QuotaKind::MaxWeeklyDuration, used, requested, limit.seconds)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// usage + requested must stay within limit, a limit of 0 is unlimited
+fn exceed(kind: QuotaKind, usage: i64, requested: i64, limit: i64) -> Result<(), Error> {
+    if limit > 0 && usage + requested > limit {
+        return Err(Error::QuotaExceeded(QuotaUsage { kind, usage, limit }));
+    }
+    Ok(())
+}
+
+/// the weeks [monday 00:00, next monday 00:00) in tz which overlap [start, end)
+fn weeks_of(tz: &Tz, start: DateTime<Utc>, end: DateTime<Utc>) -> Vec<Window> {
+    let day = start.with_timezone(tz).date_naive();
+    let mut monday = day - Duration::days(day.weekday().num_days_from_monday() as i64);
+    let mut weeks = vec![];
+    loop {
+        let next = monday + Duration::days(7);
+        let week = (start_of_day(tz, monday), start_of_day(tz, next));
+        if week.0 >= end {
+            return weeks;
+        }
+        weeks.push(week);
+        monday = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn week_should_start_on_monday() {
+        let weeks = weeks_of(
+            &Tz::UTC,
+            utc("2023-06-18T23:00:00Z"),
+            utc("2023-06-18T23:30:00Z"),
+        );
+        assert_eq!(
+            weeks,
+            vec![(utc("2023-06-12T00:00:00Z"), utc("2023-06-19T00:00:00Z"))]
+        );
+    }
+
+    #[test]
+    fn weeks_should_cover_the_whole_reservation_in_local_time() {
+        // sunday 23:00 to the next sunday 23:00 in berlin touches two local weeks
+        let tz: Tz = "Europe/Berlin".parse().unwrap();
+        let weeks = weeks_of(
+            &tz,
+            utc("2023-06-18T21:00:00Z"),
+            utc("2023-06-25T21:00:00Z"),
+        );
+        assert_eq!(
+            weeks,
+            vec![
+                (utc("2023-06-11T22:00:00Z"), utc("2023-06-18T22:00:00Z")),
+                (utc("2023-06-18T22:00:00Z"), utc("2023-06-25T22:00:00Z")),
+            ]
+        );
+
+        // ending right at a week boundary doesn't touch the next week
+        let weeks = weeks_of(
+            &tz,
+            utc("2023-06-18T21:00:00Z"),
+            utc("2023-06-18T22:00:00Z"),
+        );
+        assert_eq!(weeks.len(), 1);
+    }
+
+    #[test]
+    fn zero_limit_should_be_unlimited() {
+        assert!(exceed(QuotaKind::MaxActive, 100, 1, 0).is_ok());
+        assert!(exceed(QuotaKind::MaxActive, 1, 1, 2).is_ok());
+        assert_eq!(
+            exceed(QuotaKind::MaxActive, 2, 1, 2).unwrap_err(),
+            Error::QuotaExceeded(QuotaUsage {
+                kind: QuotaKind::MaxActive,
+                usage: 2,
+                limit: 2
+            })
+        );
+    }
+}
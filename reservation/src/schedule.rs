@@ -0,0 +1,286 @@
+//! opening hours and blackout dates of a resource, evaluated in the resource's time zone
+
+use abi::{Error, Resource};
+use chrono::{DateTime, Datelike, Duration, LocalResult, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+/// a half open window [start, end)
+pub type Window = (DateTime<Utc>, DateTime<Utc>);
+
+/// the windows within [start, end) in which the resource can be booked, sorted and merged
+pub fn open_windows(
+    resource: &Resource,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<Vec<Window>, Error> {
+    let tz = resource.get_time_zone()?;
+
+    let mut windows = if resource.opening_hours.is_empty() {
+        vec![(start, end)]
+    } else {
+        let hours = resource
+            .opening_hours
+            .iter()
+            .map(|h| Ok((h.get_weekday()?, h.get_hours()?)))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        // one extra local day on each side, the utc range may start or end on another local date
+        let first = start.with_timezone(&tz).date_naive().pred_opt().unwrap();
+        let last = end.with_timezone(&tz).date_naive().succ_opt().unwrap();
+
+        let mut windows = Vec::new();
+        for day in first.iter_days().take_while(|d| *d <= last) {
+            for (weekday, (open, close)) in hours.iter() {
+                if day.weekday() != *weekday {
+                    continue;
+                }
+                let from = local_to_utc(&tz, day.and_time(*open));
+                let to = match close {
+                    Some(close) => local_to_utc(&tz, day.and_time(*close)),
+                    None => start_of_day(&tz, day.succ_opt().unwrap()),
+                };
+                windows.push((from, to));
+            }
+        }
+        windows
+    };
+
+    let blackouts: Vec<Window> = resource
+        .get_blackout_dates()?
+        .into_iter()
+        .map(|d| {
+            (
+                start_of_day(&tz, d),
+                start_of_day(&tz, d.succ_opt().unwrap()),
+            )
+        })
+        .collect();
+    windows = subtract(windows, &blackouts);
+
+    Ok(merge(clip(windows, start, end)))
+}
+
+/// make sure [start, end) lies within a single open window of the resource
+pub fn check_open(
+    resource: &Resource,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<(), Error> {
+    if resource.opening_hours.is_empty() && resource.blackout_dates.is_empty() {
+        return Ok(());
+    }
+
+    let windows = open_windows(resource, start, end)?;
+    if windows.iter().any(|(s, e)| *s <= start && *e >= end) {
+        Ok(())
+    } else {
+        Err(Error::OutsideOpeningHours)
+    }
+}
+
+/// remove the busy windows from the free ones
+pub fn subtract(free: Vec<Window>, busy: &[Window]) -> Vec<Window> {
+    busy.iter().fold(free, |free, (bs, be)| {
+        free.into_iter()
+            .flat_map(|(s, e)| {
+                if *be <= s || *bs >= e {
+                    return vec![(s, e)];
+                }
+                let mut parts = Vec::with_capacity(2);
+                if s < *bs {
+                    parts.push((s, *bs));
+                }
+                if *be < e {
+                    parts.push((*be, e));
+                }
+                parts
+            })
+            .collect()
+    })
+}
+
+/// sort windows and merge the ones that overlap or touch
+pub fn merge(mut windows: Vec<Window>) -> Vec<Window> {
+    windows.sort();
+    let mut ret: Vec<Window> = Vec::with_capacity(windows.len());
+    for (s, e) in windows {
+        match ret.last_mut() {
+            Some(last) if s <= last.1 => last.1 = last.1.max(e),
+            _ => ret.push((s, e)),
+        }
+    }
+    ret
+}
+
+/// the start of a slot of length in every free window long enough, placed as close to start
+/// as the window allows, nearest first
+pub fn nearest_slots(
+    free: &[Window],
+    start: DateTime<Utc>,
+    length: Duration,
+) -> Vec<DateTime<Utc>> {
+    let mut slots: Vec<DateTime<Utc>> = free
+        .iter()
+        .filter(|(s, e)| *e - *s >= length)
+        .map(|(s, e)| start.clamp(*s, *e - length))
+        .collect();
+    slots.sort_by_key(|s| ((*s - start).abs(), *s));
+    slots
+}
+
+fn clip(windows: Vec<Window>, start: DateTime<Utc>, end: DateTime<Utc>) -> Vec<Window> {
+    windows
+        .into_iter()
+        .map(|(s, e)| (s.max(start), e.min(end)))
+        .filter(|(s, e)| s < e)
+        .collect()
+}
+
+/// local midnight of day in tz
+pub fn start_of_day(tz: &Tz, day: NaiveDate) -> DateTime<Utc> {
+    local_to_utc(tz, day.and_hms_opt(0, 0, 0).unwrap())
+}
+
+/// map a local wall clock time to utc, the earlier instant wins when the clock goes back,
+/// a time skipped when the clock goes forward is moved past the gap
+fn local_to_utc(tz: &Tz, local: NaiveDateTime) -> DateTime<Utc> {
+    let mut local = local;
+    loop {
+        match tz.from_local_datetime(&local) {
+            LocalResult::Single(t) | LocalResult::Ambiguous(t, _) => return t.with_timezone(&Utc),
+            LocalResult::None => local += Duration::minutes(15),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use abi::OpeningHours;
+    use chrono::Weekday;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    fn office() -> Resource {
+        Resource {
+            time_zone: "Europe/Berlin".to_string(),
+            opening_hours: vec![
+                OpeningHours::new(Weekday::Sat, "09:00", "17:00"),
+                OpeningHours::new(Weekday::Sun, "20:00", "24:00"),
+                OpeningHours::new(Weekday::Mon, "00:00", "02:00"),
+            ],
+            ..Resource::new("office")
+        }
+    }
+
+    #[test]
+    fn open_windows_should_follow_local_time_across_dst() {
+        // 2023-03-26 the clocks in Berlin go from +01:00 to +02:00
+        let windows = open_windows(
+            &office(),
+            utc("2023-03-25T00:00:00Z"),
+            utc("2023-03-28T00:00:00Z"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            windows,
+            vec![
+                (utc("2023-03-25T08:00:00Z"), utc("2023-03-25T16:00:00Z")),
+                // sunday evening and monday night are merged at local midnight
+                (utc("2023-03-26T18:00:00Z"), utc("2023-03-27T00:00:00Z")),
+            ]
+        );
+    }
+
+    #[test]
+    fn blackout_dates_should_be_closed() {
+        let resource = Resource {
+            blackout_dates: vec!["2023-03-26".to_string()],
+            ..office()
+        };
+        let windows = open_windows(
+            &resource,
+            utc("2023-03-25T00:00:00Z"),
+            utc("2023-03-28T00:00:00Z"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            windows,
+            vec![
+                (utc("2023-03-25T08:00:00Z"), utc("2023-03-25T16:00:00Z")),
+                (utc("2023-03-26T22:00:00Z"), utc("2023-03-27T00:00:00Z")),
+            ]
+        );
+    }
+
+    #[test]
+    fn check_open_should_work() {
+        let resource = office();
+        assert!(check_open(
+            &resource,
+            utc("2023-03-25T09:00:00Z"),
+            utc("2023-03-25T16:00:00Z")
+        )
+        .is_ok());
+        assert!(check_open(
+            &resource,
+            utc("2023-03-26T20:00:00Z"),
+            utc("2023-03-26T23:00:00Z")
+        )
+        .is_ok());
+        assert_eq!(
+            check_open(
+                &resource,
+                utc("2023-03-25T15:00:00Z"),
+                utc("2023-03-25T17:00:00Z")
+            )
+            .unwrap_err(),
+            Error::OutsideOpeningHours
+        );
+
+        // no schedule means always open
+        assert!(check_open(
+            &Resource::new("room"),
+            utc("2023-03-25T15:00:00Z"),
+            utc("2023-03-29T17:00:00Z")
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn nearest_slots_should_fit_the_length() {
+        let free = vec![
+            (utc("2023-01-01T08:00:00Z"), utc("2023-01-01T09:30:00Z")),
+            (utc("2023-01-01T10:00:00Z"), utc("2023-01-01T10:30:00Z")),
+            (utc("2023-01-01T13:00:00Z"), utc("2023-01-01T18:00:00Z")),
+        ];
+        assert_eq!(
+            nearest_slots(&free, utc("2023-01-01T10:00:00Z"), Duration::hours(1)),
+            vec![utc("2023-01-01T08:30:00Z"), utc("2023-01-01T13:00:00Z")]
+        );
+        assert_eq!(
+            nearest_slots(&free, utc("2023-01-01T15:00:00Z"), Duration::hours(1)),
+            vec![utc("2023-01-01T15:00:00Z"), utc("2023-01-01T08:30:00Z")]
+        );
+    }
+
+    #[test]
+    fn subtract_should_split_windows() {
+        let free = vec![(utc("2023-01-01T08:00:00Z"), utc("2023-01-01T18:00:00Z"))];
+        let busy = vec![
+            (utc("2023-01-01T07:00:00Z"), utc("2023-01-01T09:00:00Z")),
+            (utc("2023-01-01T12:00:00Z"), utc("2023-01-01T13:00:00Z")),
+        ];
+        assert_eq!(
+            subtract(free, &busy),
+            vec![
+                (utc("2023-01-01T09:00:00Z"), utc("2023-01-01T12:00:00Z")),
+                (utc("2023-01-01T13:00:00Z"), utc("2023-01-01T18:00:00Z")),
+            ]
+        );
+    }
+}
@@ -2,25 +2,44 @@ use std::{
     ops::Deref,
     pin::Pin,
     task::{Context, Poll},
+    time::Duration,
 };
 
 use abi::{
     reservation_service_server::{ReservationService, ReservationServiceServer},
-    CancelRequest, CancelResponse, Config, ConfirmRequest, ConfirmResponse, Error, FilterRequest,
-    FilterResponse, GetRequest, GetResponse, ListenRequest, QueryRequest, ReserveRequest,
-    ReserveResponse, UpdateRequest, UpdateResponse,
+    ApproveRequest, ApproveResponse, AvailabilityRequest, AvailabilityResponse, BulkRequest,
+    BulkResponse, CancelRequest, CancelResponse, CheckResponse, Config, ConfirmRequest,
+    ConfirmResponse, ConflictDetail, Error, FilterRequest, FilterResponse, GetRequest,
+    GetResourceRequest, GetResourceResponse, GetResourceTypeRequest, GetResourceTypeResponse,
+    GetResponse, JoinWaitlistRequest, JoinWaitlistResponse, LeaveWaitlistRequest,
+    LeaveWaitlistResponse, ListenRequest, QueryRequest, RejectRequest, RejectResponse,
+    RescheduleRequest, RescheduleResponse, ReserveBatchRequest, ReserveBatchResponse,
+    ReserveRequest, ReserveResponse, SetResourceRequest, SetResourceResponse,
+    SetResourceTypeRequest, SetResourceTypeResponse, UpdateAttendeesRequest,
+    UpdateAttendeesResponse, UpdateRequest, UpdateResponse, DEFAULT_TENANT,
 };
 use futures::Stream;
 use reservation::{ReservationManager, Rsvp};
 use tokio::sync::mpsc;
 use tonic::{async_trait, transport::Server, Request, Response, Status};
+use tracing::warn;
 
 use crate::{ReservationStream, RsvpService, TonicReceiverStream};
 
+/// the metadata key carrying the tenant of a request
+pub const TENANT_HEADER: &str = "x-tenant-id";
+
+/// the metadata key carrying the idempotency key of a mutating request
+pub const IDEMPOTENCY_HEADER: &str = "idempotency-key";
+
+/// how often expired idempotency keys are purged
+const IDEMPOTENCY_PURGE_INTERVAL: Duration = Duration::from_secs(3600);
+
 pub async fn start_server(config: &Config) -> Result<(), anyhow::Error> {
     let addr = config.server.server_url().parse()?;
 
     let service = RsvpService::from_config(config).await?;
+    tokio::spawn(purge_idempotency_keys(service.with_tenant(DEFAULT_TENANT)));
     let service = ReservationServiceServer::new(service);
 
     println!("Listening on {}", addr);
@@ -30,6 +49,17 @@ pub async fn start_server(config: &Config) -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+/// forget expired idempotency keys of every tenant once in a while, instead of on every request
+async fn purge_idempotency_keys(manager: ReservationManager) {
+    let mut interval = tokio::time::interval(IDEMPOTENCY_PURGE_INTERVAL);
+    loop {
+        interval.tick().await;
+        if let Err(e) = manager.purge_idempotency_keys().await {
+            warn!("Failed to purge idempotency keys: {}", e);
+        }
+    }
+}
+
 impl Deref for RsvpService {
     type Target = ReservationManager;
 
@@ -44,6 +74,19 @@ impl RsvpService {
             manager: ReservationManager::from_config(&config.db).await?,
         })
     }
+
+    /// a manager for the tenant in the `x-tenant-id` metadata, the default tenant if there is none
+    fn manager<T>(&self, request: &Request<T>) -> Result<ReservationManager, Error> {
+        let tenant = match request.metadata().get(TENANT_HEADER) {
+            None => DEFAULT_TENANT,
+            Some(v) => v
+                .to_str()
+                .ok()
+                .filter(|t| !t.trim().is_empty())
+                .ok_or_else(|| Error::InvalidTenantId(format!("{:?}", v)))?,
+        };
+        Ok(self.manager.with_tenant(tenant))
+    }
 }
 
 #[async_trait]
@@ -52,14 +95,81 @@ impl ReservationService for RsvpService {
         &self,
         request: Request<ReserveRequest>,
     ) -> Result<Response<ReserveResponse>, Status> {
-        let request = request.into_inner();
+        let manager = self.manager(&request)?;
+        let key = idempotency_key(&request)?;
+        let request = &request.into_inner();
         if request.reservation.is_none() {
             return Err(Error::MissingArgument("reservation".to_string()).into());
         }
-        let reservation = self.manager.reserve(request.reservation.unwrap()).await?;
-        Ok(Response::new(ReserveResponse {
-            reservation: Some(reservation),
-        }))
+        let resp = manager
+            .idempotent(key.as_deref(), "reserve", request, |manager| async move {
+                let rsvp = request.reservation.clone().unwrap();
+                let reservation = match manager.reserve(rsvp.clone()).await {
+                    Err(Error::ConflictReservation(mut info)) if request.max_suggestions > 0 => {
+                        let max = request.max_suggestions as usize;
+                        // suggestions are extra, failing to find them must not hide the conflict
+                        let suggestions = manager.suggest(rsvp, max).await.unwrap_or_else(|e| {
+                            warn!("Failed to suggest alternatives: {}", e);
+                            vec![]
+                        });
+                        info.set_suggestions(suggestions);
+                        return Err(Error::ConflictReservation(info));
+                    }
+                    ret => ret?,
+                };
+                Ok(ReserveResponse {
+                    reservation: Some(reservation),
+                })
+            })
+            .await?;
+        Ok(Response::new(resp))
+    }
+
+    /// check a reservation without making it, conflicts are part of the response
+    async fn check(
+        &self,
+        request: Request<ReserveRequest>,
+    ) -> Result<Response<CheckResponse>, Status> {
+        let manager = self.manager(&request)?;
+        let request = request.into_inner();
+        let rsvp = match request.reservation {
+            Some(rsvp) => rsvp,
+            None => return Err(Error::MissingArgument("reservation".to_string()).into()),
+        };
+        let resp = match manager.check(rsvp.clone()).await {
+            Ok(reservation) => CheckResponse {
+                reservation: Some(reservation),
+                conflicts: vec![],
+            },
+            Err(Error::ConflictReservation(info)) => CheckResponse {
+                reservation: Some(rsvp),
+                conflicts: vec![ConflictDetail::new(0, &info)],
+            },
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Response::new(resp))
+    }
+
+    /// make several reservations in one transaction
+    async fn reserve_batch(
+        &self,
+        request: Request<ReserveBatchRequest>,
+    ) -> Result<Response<ReserveBatchResponse>, Status> {
+        let manager = self.manager(&request)?;
+        let key = idempotency_key(&request)?;
+        let request = &request.into_inner();
+        let resp = manager
+            .idempotent(
+                key.as_deref(),
+                "reserve_batch",
+                request,
+                |manager| async move {
+                    let reservations = manager.reserve_many(request.reservations.clone()).await?;
+                    Ok(ReserveBatchResponse { reservations })
+                },
+            )
+            .await?;
+        Ok(Response::new(resp))
     }
 
     /// confirm a reservation
@@ -67,11 +177,62 @@ impl ReservationService for RsvpService {
         &self,
         request: Request<ConfirmRequest>,
     ) -> Result<Response<ConfirmResponse>, Status> {
-        let request = request.into_inner();
-        let rsvp = self.manager.change_status(request.id).await?;
-        Ok(Response::new(ConfirmResponse {
-            reservation: Some(rsvp),
-        }))
+        let manager = self.manager(&request)?;
+        let key = idempotency_key(&request)?;
+        let request = &request.into_inner();
+        let resp = manager
+            .idempotent(key.as_deref(), "confirm", request, |manager| async move {
+                let rsvp = manager
+                    .change_status(request.id, request.expected_version)
+                    .await?;
+                Ok(ConfirmResponse {
+                    reservation: Some(rsvp),
+                })
+            })
+            .await?;
+        Ok(Response::new(resp))
+    }
+
+    /// approve a reservation waiting for approval
+    async fn approve(
+        &self,
+        request: Request<ApproveRequest>,
+    ) -> Result<Response<ApproveResponse>, Status> {
+        let manager = self.manager(&request)?;
+        let key = idempotency_key(&request)?;
+        let request = &request.into_inner();
+        let resp = manager
+            .idempotent(key.as_deref(), "approve", request, |manager| async move {
+                let rsvp = manager
+                    .approve(request.id, request.approver.clone())
+                    .await?;
+                Ok(ApproveResponse {
+                    reservation: Some(rsvp),
+                })
+            })
+            .await?;
+        Ok(Response::new(resp))
+    }
+
+    /// reject a reservation waiting for approval
+    async fn reject(
+        &self,
+        request: Request<RejectRequest>,
+    ) -> Result<Response<RejectResponse>, Status> {
+        let manager = self.manager(&request)?;
+        let key = idempotency_key(&request)?;
+        let request = &request.into_inner();
+        let resp = manager
+            .idempotent(key.as_deref(), "reject", request, |manager| async move {
+                let rsvp = manager
+                    .reject(request.id, request.approver.clone(), request.reason.clone())
+                    .await?;
+                Ok(RejectResponse {
+                    reservation: Some(rsvp),
+                })
+            })
+            .await?;
+        Ok(Response::new(resp))
     }
 
     /// update a reservation
@@ -79,11 +240,90 @@ impl ReservationService for RsvpService {
         &self,
         request: Request<UpdateRequest>,
     ) -> Result<Response<UpdateResponse>, Status> {
-        let request = request.into_inner();
-        let rsvp = self.manager.update_note(request.id, request.note).await?;
-        Ok(Response::new(UpdateResponse {
-            reservation: Some(rsvp),
-        }))
+        let manager = self.manager(&request)?;
+        let key = idempotency_key(&request)?;
+        let request = &request.into_inner();
+        if request.update_mask.is_none() {
+            return Err(Error::MissingArgument("update_mask".to_string()).into());
+        }
+        let resp = manager
+            .idempotent(key.as_deref(), "update", request, |manager| async move {
+                let rsvp = manager
+                    .update(
+                        request.id,
+                        request.reservation.clone().unwrap_or_default(),
+                        request.update_mask.clone().unwrap(),
+                        request.expected_version,
+                    )
+                    .await?;
+                Ok(UpdateResponse {
+                    reservation: Some(rsvp),
+                })
+            })
+            .await?;
+        Ok(Response::new(resp))
+    }
+
+    /// move a reservation to a new window
+    async fn reschedule(
+        &self,
+        request: Request<RescheduleRequest>,
+    ) -> Result<Response<RescheduleResponse>, Status> {
+        let manager = self.manager(&request)?;
+        let key = idempotency_key(&request)?;
+        let request = &request.into_inner();
+        let (start, end) = match (request.start.clone(), request.end.clone()) {
+            (Some(start), Some(end)) => (start, end),
+            _ => return Err(Error::InvalidTimespan.into()),
+        };
+        let resp = manager
+            .idempotent(
+                key.as_deref(),
+                "reschedule",
+                request,
+                |manager| async move {
+                    let rsvp = manager
+                        .reschedule(request.id, start, end, request.resource_id.clone())
+                        .await?;
+                    Ok(RescheduleResponse {
+                        reservation: Some(rsvp),
+                    })
+                },
+            )
+            .await?;
+        Ok(Response::new(resp))
+    }
+
+    /// add or remove attendees, or answer an invitation
+    async fn update_attendees(
+        &self,
+        request: Request<UpdateAttendeesRequest>,
+    ) -> Result<Response<UpdateAttendeesResponse>, Status> {
+        let manager = self.manager(&request)?;
+        let key = idempotency_key(&request)?;
+        let request = &request.into_inner();
+        let resp = manager
+            .idempotent(
+                key.as_deref(),
+                "update_attendees",
+                request,
+                |manager| async move {
+                    let rsvp = manager
+                        .update_attendees(
+                            request.id,
+                            request.user_id.clone(),
+                            request.add.clone(),
+                            request.remove.clone(),
+                            request.expected_version,
+                        )
+                        .await?;
+                    Ok(UpdateAttendeesResponse {
+                        reservation: Some(rsvp),
+                    })
+                },
+            )
+            .await?;
+        Ok(Response::new(resp))
     }
 
     ///  cancel a reservation
@@ -91,17 +331,117 @@ impl ReservationService for RsvpService {
         &self,
         request: Request<CancelRequest>,
     ) -> Result<Response<CancelResponse>, Status> {
-        let request = request.into_inner();
-        let rsvp = self.manager.delete(request.id).await?;
-        Ok(Response::new(CancelResponse {
-            reservation: Some(rsvp),
-        }))
+        let manager = self.manager(&request)?;
+        let key = idempotency_key(&request)?;
+        let request = &request.into_inner();
+        let resp = manager
+            .idempotent(key.as_deref(), "cancel", request, |manager| async move {
+                let rsvp = manager.delete(request.id, request.expected_version).await?;
+                Ok(CancelResponse {
+                    reservation: Some(rsvp),
+                })
+            })
+            .await?;
+        Ok(Response::new(resp))
+    }
+
+    /// confirm many reservations at once
+    async fn bulk_confirm(
+        &self,
+        request: Request<BulkRequest>,
+    ) -> Result<Response<BulkResponse>, Status> {
+        let manager = self.manager(&request)?;
+        let key = idempotency_key(&request)?;
+        let request = &request.into_inner();
+        let resp = manager
+            .idempotent(
+                key.as_deref(),
+                "bulk_confirm",
+                request,
+                |manager| async move {
+                    let results = manager.confirm_many(request.clone()).await?;
+                    Ok(BulkResponse { results })
+                },
+            )
+            .await?;
+        Ok(Response::new(resp))
+    }
+
+    /// cancel many reservations at once
+    async fn bulk_cancel(
+        &self,
+        request: Request<BulkRequest>,
+    ) -> Result<Response<BulkResponse>, Status> {
+        let manager = self.manager(&request)?;
+        let key = idempotency_key(&request)?;
+        let request = &request.into_inner();
+        let resp = manager
+            .idempotent(
+                key.as_deref(),
+                "bulk_cancel",
+                request,
+                |manager| async move {
+                    let results = manager.delete_many(request.clone()).await?;
+                    Ok(BulkResponse { results })
+                },
+            )
+            .await?;
+        Ok(Response::new(resp))
+    }
+
+    /// wait for a fully booked window
+    async fn join_waitlist(
+        &self,
+        request: Request<JoinWaitlistRequest>,
+    ) -> Result<Response<JoinWaitlistResponse>, Status> {
+        let manager = self.manager(&request)?;
+        let key = idempotency_key(&request)?;
+        let request = &request.into_inner();
+        if request.reservation.is_none() {
+            return Err(Error::MissingArgument("reservation".to_string()).into());
+        }
+        let resp = manager
+            .idempotent(
+                key.as_deref(),
+                "join_waitlist",
+                request,
+                |manager| async move {
+                    manager
+                        .join_waitlist(request.reservation.clone().unwrap())
+                        .await
+                },
+            )
+            .await?;
+        Ok(Response::new(resp))
+    }
+
+    /// stop waiting for a window
+    async fn leave_waitlist(
+        &self,
+        request: Request<LeaveWaitlistRequest>,
+    ) -> Result<Response<LeaveWaitlistResponse>, Status> {
+        let manager = self.manager(&request)?;
+        let key = idempotency_key(&request)?;
+        let request = &request.into_inner();
+        let resp = manager
+            .idempotent(
+                key.as_deref(),
+                "leave_waitlist",
+                request,
+                |manager| async move {
+                    let entry = manager.leave_waitlist(request.id).await?;
+                    Ok(LeaveWaitlistResponse { entry: Some(entry) })
+                },
+            )
+            .await?;
+        Ok(Response::new(resp))
     }
 
     /// get a reservation
     async fn get(&self, request: Request<GetRequest>) -> Result<Response<GetResponse>, Status> {
+        let manager = self.manager(&request)?;
         let request = request.into_inner();
-        let rsvp = self.manager.get(request.id).await?;
+        let rsvp = manager.get(request.id).await?;
         Ok(Response::new(GetResponse {
             reservation: Some(rsvp),
         }))
@@ -115,11 +455,12 @@ impl ReservationService for RsvpService {
         &self,
         request: Request<QueryRequest>,
     ) -> Result<Response<Self::queryStream>, Status> {
+        let manager = self.manager(&request)?;
         let request = request.into_inner();
         if request.query.is_none() {
             return Err(Error::MissingArgument("missing argument: query".to_string()).into());
         }
-        let rx = self.manager.query(request.query.unwrap()).await;
+        let rx = manager.query(request.query.unwrap()).await;
         let stream = TonicReceiverStream::new(rx);
         Ok(Response::new(Box::pin(stream)))
     }
@@ -129,18 +470,99 @@ impl ReservationService for RsvpService {
         &self,
         request: Request<FilterRequest>,
     ) -> Result<Response<FilterResponse>, Status> {
+        let manager = self.manager(&request)?;
         let request = request.into_inner();
         if request.filter.is_none() {
             return Err(Error::MissingArgument("filter".to_string()).into());
         }
         let filter = request.filter.unwrap();
-        let (pager, rsvps) = self.manager.filter(filter).await?;
+        let (pager, rsvps) = manager.filter(filter).await?;
         Ok(Response::new(FilterResponse {
             reservations: rsvps,
             pager: Some(pager),
         }))
     }
 
+    /// create or change the settings of a resource
+    async fn set_resource(
+        &self,
+        request: Request<SetResourceRequest>,
+    ) -> Result<Response<SetResourceResponse>, Status> {
+        let manager = self.manager(&request)?;
+        let request = request.into_inner();
+        if request.resource.is_none() {
+            return Err(Error::MissingArgument("resource".to_string()).into());
+        }
+        let resource = manager.set_resource(request.resource.unwrap()).await?;
+        Ok(Response::new(SetResourceResponse {
+            resource: Some(resource),
+        }))
+    }
+
+    /// get the settings of a resource
+    async fn get_resource(
+        &self,
+        request: Request<GetResourceRequest>,
+    ) -> Result<Response<GetResourceResponse>, Status> {
+        let manager = self.manager(&request)?;
+        let request = request.into_inner();
+        let resource = manager.get_resource(request.id).await?;
+        Ok(Response::new(GetResourceResponse {
+            resource: Some(resource),
+        }))
+    }
+
+    /// create or change a resource type
+    async fn set_resource_type(
+        &self,
+        request: Request<SetResourceTypeRequest>,
+    ) -> Result<Response<SetResourceTypeResponse>, Status> {
+        let manager = self.manager(&request)?;
+        let request = request.into_inner();
+        if request.resource_type.is_none() {
+            return Err(Error::MissingArgument("resource_type".to_string()).into());
+        }
+        let resource_type = manager
+            .set_resource_type(request.resource_type.unwrap())
+            .await?;
+        Ok(Response::new(SetResourceTypeResponse {
+            resource_type: Some(resource_type),
+        }))
+    }
+
+    /// get a resource type
+    async fn get_resource_type(
+        &self,
+        request: Request<GetResourceTypeRequest>,
+    ) -> Result<Response<GetResourceTypeResponse>, Status> {
+        let manager = self.manager(&request)?;
+        let request = request.into_inner();
+        let resource_type = manager.get_resource_type(request.id).await?;
+        Ok(Response::new(GetResourceTypeResponse {
+            resource_type: Some(resource_type),
+        }))
+    }
+
+    /// free windows of a resource within the given range
+    async fn availability(
+        &self,
+        request: Request<AvailabilityRequest>,
+    ) -> Result<Response<AvailabilityResponse>, Status> {
+        let manager = self.manager(&request)?;
+        let request = request.into_inner();
+        if request.start.is_none() || request.end.is_none() {
+            return Err(Error::InvalidTimespan.into());
+        }
+        let windows = manager
+            .availability(
+                request.resource_id,
+                request.start.unwrap(),
+                request.end.unwrap(),
+            )
+            .await?;
+        Ok(Response::new(AvailabilityResponse { windows }))
+    }
+
     /// Server streaming response type for the listen method.
     type listenStream = ReservationStream;
 
@@ -153,6 +575,17 @@ impl ReservationService for RsvpService {
     }
 }
 
+/// the idempotency key of the request, if the client sent one
+fn idempotency_key<T>(request: &Request<T>) -> Result<Option<String>, Error> {
+    match request.metadata().get(IDEMPOTENCY_HEADER) {
+        None => Ok(None),
+        Some(v) => v
+            .to_str()
+            .map(|k| Some(k.to_string()))
+            .map_err(|_| Error::InvalidIdempotencyKey(format!("{:?}", v))),
+    }
+}
+
 impl<T> TonicReceiverStream<T> {
     pub fn new(inner: mpsc::Receiver<Result<T, Error>>) -> Self {
         Self { inner }
@@ -191,9 +624,7 @@ mod tests {
             "2022-02-04T15:00:01-0800".parse().unwrap(),
             "xxl-note",
         );
-        let request = tonic::Request::new(ReserveRequest {
-            reservation: Some(reservation.clone()),
-        });
+        let request = tonic::Request::new(ReserveRequest::new(reservation.clone()));
         let resp = service.reserve(request).await.unwrap();
         let rsvp = resp.into_inner().reservation;
         assert!(rsvp.is_some());
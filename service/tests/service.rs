@@ -5,13 +5,17 @@ mod test_utils;
 use std::time::Duration;
 
 use abi::{
-    reservation_service_client::ReservationServiceClient, CancelRequest, Config, ConfirmRequest,
-    FilterRequest, GetRequest, QueryRequest, Reservation, ReservationFilterBuilder,
-    ReservationQueryBuilder, ReserveRequest, UpdateRequest,
+    reservation_service_client::ReservationServiceClient, ApproveRequest, Attendee, AttendeeRole,
+    AvailabilityRequest, BookingPolicy, BulkRequest, CancelRequest, Config, ConfirmRequest,
+    ConflictDetails, ConflictReason, FilterRequest, GetRequest, JoinWaitlistRequest, OpeningHours,
+    QueryRequest, RejectRequest, RescheduleRequest, Reservation, ReservationFilterBuilder,
+    ReservationQueryBuilder, ReserveBatchRequest, ReserveRequest, Resource, ResourceType,
+    SetResourceRequest, SetResourceTypeRequest, UpdateAttendeesRequest, UpdateRequest,
 };
 use futures::StreamExt;
 use reservation_service::start_server;
 use tokio::time;
+use tonic::IntoRequest;
 
 use test_utils::TestConfig;
 
@@ -36,6 +40,14 @@ async fn grpc_reserve_should_work() {
         .reservation
         .unwrap();
     rsvp1.id = ret1.id;
+    assert_eq!(ret1.version, 1);
+    assert!(ret1.created_at.is_some());
+    rsvp1.created_at = ret1.created_at.clone();
+    rsvp1.updated_at = ret1.updated_at.clone();
+    rsvp1.version = ret1.version;
+    // the resource is unknown, so the reservation is kept in UTC
+    assert_eq!(ret1.time_zone, "UTC");
+    rsvp1.time_zone = ret1.time_zone.clone();
     assert_eq!(ret1, rsvp1);
 
     // 2. then reserve a conflict reservation. should fail
@@ -48,7 +60,16 @@ async fn grpc_reserve_should_work() {
     );
     let ret2 = client.reserve(ReserveRequest::new(rsvp2.clone())).await;
     assert!(ret2.is_err());
-    assert_eq!(ret2.unwrap_err().code(), tonic::Code::AlreadyExists);
+    let err = ret2.unwrap_err();
+    assert_eq!(err.code(), tonic::Code::AlreadyExists);
+    let details = ConflictDetails::from_status(&err).unwrap();
+    let conflict = &details.conflicts[0];
+    assert_eq!(conflict.reason, ConflictReason::Overlap as i32);
+    let existing = conflict.existing.as_ref().unwrap();
+    assert_eq!(existing.reservation_id, rsvp1.id);
+    assert_eq!(existing.start, rsvp1.start);
+    assert_eq!(conflict.overlapping, vec![existing.clone()]);
+    assert_eq!(conflict.requested.as_ref().unwrap().resource_id, "test-rid");
 
     // 3. confirm the first reservation
     let ret3 = client
@@ -61,6 +82,76 @@ async fn grpc_reserve_should_work() {
     assert_eq!(ret3.status, abi::ReservationStatus::Confirmed as i32);
 }
 
+#[tokio::test]
+async fn grpc_reserve_batch_should_work() {
+    let config = TestConfig::with_server_port(50008);
+    let mut client = get_test_cliet(&config).await;
+
+    let rsvps: Vec<_> = ["test-room", "test-projector"]
+        .iter()
+        .map(|rid| {
+            Reservation::new(
+                "test-uid",
+                *rid,
+                "2023-01-09T10:10:10-0800".parse().unwrap(),
+                "2023-01-09T12:10:10-0800".parse().unwrap(),
+                "test-meeting",
+            )
+        })
+        .collect();
+    let ret = client
+        .reserve_batch(ReserveBatchRequest::new(rsvps.clone()))
+        .await
+        .unwrap()
+        .into_inner()
+        .reservations;
+    assert_eq!(ret.len(), 2);
+    assert_eq!(ret[0].resource_id, "test-room");
+    assert_eq!(ret[1].resource_id, "test-projector");
+
+    // the projector is taken now, so the room of the second batch should not be reserved either
+    let rsvps: Vec<_> = ["test-room-2", "test-projector"]
+        .iter()
+        .map(|rid| {
+            Reservation::new(
+                "test-uid2",
+                *rid,
+                "2023-01-09T11:10:10-0800".parse().unwrap(),
+                "2023-01-09T13:10:10-0800".parse().unwrap(),
+                "test-meeting-2",
+            )
+        })
+        .collect();
+    let err = client
+        .reserve_batch(ReserveBatchRequest::new(rsvps))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::AlreadyExists);
+    assert!(err.message().contains("item 1"));
+    let details = ConflictDetails::from_status(&err).unwrap();
+    assert_eq!(details.conflicts.len(), 1);
+    assert_eq!(details.conflicts[0].index, 1);
+    assert_eq!(
+        details.conflicts[0]
+            .existing
+            .as_ref()
+            .unwrap()
+            .reservation_id,
+        ret[1].id
+    );
+
+    let filter = ReservationFilterBuilder::default()
+        .user_id("test-uid2")
+        .build()
+        .unwrap();
+    let ret = client
+        .filter(FilterRequest::new(filter))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(ret.reservations.is_empty());
+}
+
 #[tokio::test]
 async fn grpc_query_should_work() {
     let config = TestConfig::with_server_port(50002);
@@ -111,6 +202,535 @@ async fn grpc_cancel_should_work() {
     assert_eq!(ret.status, abi::ReservationStatus::Pending as i32);
 }
 
+#[tokio::test]
+async fn grpc_bulk_confirm_and_cancel_should_work() {
+    let config = TestConfig::with_server_port(50009);
+    let mut client = get_test_cliet(&config).await;
+    make_reservations(&mut client, 10).await;
+
+    let ret = client
+        .bulk_confirm(BulkRequest::with_ids(vec![1, 2, 42]))
+        .await
+        .unwrap()
+        .into_inner()
+        .results;
+    assert_eq!(ret.len(), 3);
+    assert_eq!(
+        ret[0].reservation.as_ref().unwrap().status,
+        abi::ReservationStatus::Confirmed as i32
+    );
+    assert!(ret[1].error.is_empty());
+    assert!(ret[2].reservation.is_none());
+    assert!(!ret[2].error.is_empty());
+
+    // cancel every booking of test-rid-3 in the window
+    let query = ReservationQueryBuilder::default()
+        .resource_id("test-rid-3")
+        .start(
+            "2023-01-09T00:00:00-0800"
+                .parse::<prost_types::Timestamp>()
+                .unwrap(),
+        )
+        .end(
+            "2023-01-16T00:00:00-0800"
+                .parse::<prost_types::Timestamp>()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+    let ret = client
+        .bulk_cancel(BulkRequest::with_query(query))
+        .await
+        .unwrap()
+        .into_inner()
+        .results;
+    assert_eq!(ret.len(), 1);
+    assert_eq!(ret[0].id, 3);
+
+    let err = client.get(GetRequest::new(3)).await.unwrap_err();
+    assert_eq!(err.code(), tonic::Code::NotFound);
+}
+
+#[tokio::test]
+async fn grpc_waitlist_should_work() {
+    let config = TestConfig::with_server_port(50010);
+    let mut client = get_test_cliet(&config).await;
+    make_reservations(&mut client, 1).await;
+
+    let rsvp = Reservation::new(
+        "test-uid",
+        "test-rid-1",
+        "2023-01-09T10:10:10-0800".parse().unwrap(),
+        "2023-01-10T10:10:10-0800".parse().unwrap(),
+        "test-waiting",
+    );
+    let err = client
+        .reserve(ReserveRequest::new(rsvp.clone()))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::AlreadyExists);
+
+    let ret = client
+        .join_waitlist(JoinWaitlistRequest::new(rsvp))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(ret.reservation.is_none());
+    assert_eq!(ret.entry.unwrap().user_id, "test-uid");
+
+    client.cancel(CancelRequest::new(1)).await.unwrap();
+
+    let filter = ReservationFilterBuilder::default()
+        .user_id("test-uid")
+        .build()
+        .unwrap();
+    let ret = client
+        .filter(FilterRequest::new(filter))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(ret.reservations.len(), 1);
+    assert_eq!(ret.reservations[0].note, "test-waiting");
+}
+
+#[tokio::test]
+async fn grpc_availability_should_work() {
+    let config = TestConfig::with_server_port(50011);
+    let mut client = get_test_cliet(&config).await;
+
+    let resource = Resource {
+        // monday
+        opening_hours: vec![OpeningHours {
+            weekday: 1,
+            open: "09:00".to_string(),
+            close: "17:00".to_string(),
+        }],
+        ..Resource::new("test-office")
+    };
+    client
+        .set_resource(SetResourceRequest::new(resource))
+        .await
+        .unwrap();
+
+    let rsvp = Reservation::new(
+        "test-uid",
+        "test-office",
+        "2023-01-02T08:00:00Z".parse().unwrap(),
+        "2023-01-02T10:00:00Z".parse().unwrap(),
+        "test-note",
+    );
+    let err = client.reserve(ReserveRequest::new(rsvp)).await.unwrap_err();
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+    let ret = client
+        .availability(AvailabilityRequest::new(
+            "test-office",
+            "2023-01-01T00:00:00Z".parse().unwrap(),
+            "2023-01-08T00:00:00Z".parse().unwrap(),
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(ret.windows.len(), 1);
+    assert_eq!(
+        ret.windows[0].start,
+        Some("2023-01-02T09:00:00Z".parse().unwrap())
+    );
+}
+
+#[tokio::test]
+async fn grpc_approval_should_work() {
+    let config = TestConfig::with_server_port(50012);
+    let mut client = get_test_cliet(&config).await;
+
+    let resource = Resource {
+        requires_approval: true,
+        approvers: vec!["boss".to_string()],
+        ..Resource::new("test-rid-1")
+    };
+    client
+        .set_resource(SetResourceRequest::new(resource))
+        .await
+        .unwrap();
+    make_reservations(&mut client, 1).await;
+
+    let err = client.confirm(ConfirmRequest::new(1)).await.unwrap_err();
+    assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+    let err = client
+        .approve(ApproveRequest::new(1, "yuzhe"))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::PermissionDenied);
+
+    let rsvp = client
+        .approve(ApproveRequest::new(1, "boss"))
+        .await
+        .unwrap()
+        .into_inner()
+        .reservation
+        .unwrap();
+    assert_eq!(rsvp.status, abi::ReservationStatus::Confirmed as i32);
+
+    let err = client
+        .reject(RejectRequest::new(1, "boss", "too late"))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::NotFound);
+}
+
+#[tokio::test]
+async fn grpc_tenant_header_should_isolate_data() {
+    let config = TestConfig::with_server_port(50013);
+    let mut client = get_test_cliet(&config).await;
+    make_reservations(&mut client, 1).await;
+
+    let mut request = GetRequest::new(1).into_request();
+    request
+        .metadata_mut()
+        .insert("x-tenant-id", "acme".parse().unwrap());
+    let err = client.get(request).await.unwrap_err();
+    assert_eq!(err.code(), tonic::Code::NotFound);
+
+    let rsvp = Reservation::new(
+        "yuzhe",
+        "test-rid-1",
+        "2023-01-09T10:10:10-0800".parse().unwrap(),
+        "2023-01-10T10:10:10-0800".parse().unwrap(),
+        "acme-node",
+    );
+    let mut request = ReserveRequest::new(rsvp).into_request();
+    request
+        .metadata_mut()
+        .insert("x-tenant-id", "acme".parse().unwrap());
+    let ret = client
+        .reserve(request)
+        .await
+        .unwrap()
+        .into_inner()
+        .reservation
+        .unwrap();
+    assert_eq!(ret.note, "acme-node");
+
+    let mut request = GetRequest::new(ret.id).into_request();
+    request
+        .metadata_mut()
+        .insert("x-tenant-id", " ".parse().unwrap());
+    let err = client.get(request).await.unwrap_err();
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn grpc_crafted_ids_should_not_read_other_tenants() {
+    let config = TestConfig::with_server_port(50018);
+    let mut client = get_test_cliet(&config).await;
+    make_reservations(&mut client, 3).await;
+
+    let crafted = "x' OR tenant_id <> '";
+    for (user_id, resource_id) in [(crafted, ""), ("", crafted), (crafted, crafted)] {
+        let query = ReservationQueryBuilder::default()
+            .user_id(user_id)
+            .resource_id(resource_id)
+            .build()
+            .unwrap();
+        let mut request = QueryRequest::new(query).into_request();
+        request
+            .metadata_mut()
+            .insert("x-tenant-id", "acme".parse().unwrap());
+        let mut ret = client.query(request).await.unwrap().into_inner();
+        assert!(ret.next().await.is_none());
+
+        let filter = ReservationFilterBuilder::default()
+            .user_id(user_id)
+            .resource_id(resource_id)
+            .build()
+            .unwrap();
+        let mut request = FilterRequest::new(filter).into_request();
+        request
+            .metadata_mut()
+            .insert("x-tenant-id", "acme".parse().unwrap());
+        let ret = client.filter(request).await.unwrap().into_inner();
+        assert!(ret.reservations.is_empty());
+    }
+
+    // the same ids are matched literally in their own tenant
+    let filter = ReservationFilterBuilder::default()
+        .user_id("yuzhe")
+        .build()
+        .unwrap();
+    let ret = client
+        .filter(FilterRequest::new(filter))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(ret.reservations.len(), 3);
+}
+
+#[tokio::test]
+async fn grpc_reject_should_use_the_request_tenant() {
+    let config = TestConfig::with_server_port(50019);
+    let mut client = get_test_cliet(&config).await;
+    let resource = Resource {
+        requires_approval: true,
+        approvers: vec!["boss".to_string()],
+        ..Resource::new("test-rid-1")
+    };
+    client
+        .set_resource(SetResourceRequest::new(resource))
+        .await
+        .unwrap();
+    make_reservations(&mut client, 1).await;
+
+    let err = client
+        .reject(acme(RejectRequest::new(1, "boss", "wrong tenant")))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::NotFound);
+    let rsvp = get_reservation(&mut client, 1).await;
+    assert_eq!(rsvp.status, abi::ReservationStatus::Pending as i32);
+}
+
+#[tokio::test]
+async fn grpc_update_should_use_the_request_tenant() {
+    let config = TestConfig::with_server_port(50020);
+    let mut client = get_test_cliet(&config).await;
+    make_reservations(&mut client, 1).await;
+
+    let rsvp = Reservation {
+        note: "acme".to_string(),
+        ..Default::default()
+    };
+    let err = client
+        .update(acme(UpdateRequest::new(1, rsvp, &["note"])))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::NotFound);
+    assert_eq!(get_reservation(&mut client, 1).await.note, "test-node-1");
+}
+
+#[tokio::test]
+async fn grpc_reschedule_should_use_the_request_tenant() {
+    let config = TestConfig::with_server_port(50021);
+    let mut client = get_test_cliet(&config).await;
+    make_reservations(&mut client, 1).await;
+
+    let start = "2023-01-11T10:10:10-0800"
+        .parse::<prost_types::Timestamp>()
+        .unwrap();
+    let end = "2023-01-12T10:10:10-0800"
+        .parse::<prost_types::Timestamp>()
+        .unwrap();
+    let err = client
+        .reschedule(acme(RescheduleRequest::new(1, start.clone(), end, None)))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::NotFound);
+    assert_ne!(get_reservation(&mut client, 1).await.start, Some(start));
+}
+
+#[tokio::test]
+async fn grpc_join_waitlist_should_use_the_request_tenant() {
+    let config = TestConfig::with_server_port(50022);
+    let mut client = get_test_cliet(&config).await;
+    make_reservations(&mut client, 1).await;
+
+    // the window is only taken in the default tenant, acme gets it right away
+    let rsvp = Reservation::new(
+        "test-uid",
+        "test-rid-1",
+        "2023-01-09T10:10:10-0800".parse().unwrap(),
+        "2023-01-10T10:10:10-0800".parse().unwrap(),
+        "test-waiting",
+    );
+    let ret = client
+        .join_waitlist(acme(JoinWaitlistRequest::new(rsvp.clone())))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(ret.entry.is_none());
+    assert_eq!(ret.reservation.unwrap().note, "test-waiting");
+
+    let ret = client
+        .join_waitlist(JoinWaitlistRequest::new(rsvp))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(ret.reservation.is_none());
+    assert!(ret.entry.is_some());
+}
+
+#[tokio::test]
+async fn grpc_set_resource_type_should_use_the_request_tenant() {
+    let config = TestConfig::with_server_port(50023);
+    let mut client = get_test_cliet(&config).await;
+
+    let desk = ResourceType::new("desk", BookingPolicy::default()).with_user_exclusive();
+    client
+        .set_resource_type(acme(SetResourceTypeRequest::new(desk)))
+        .await
+        .unwrap();
+    for id in ["desk-1", "desk-2"] {
+        let resource = Resource {
+            resource_type: "desk".to_string(),
+            ..Resource::new(id)
+        };
+        client
+            .set_resource(acme(SetResourceRequest::new(resource.clone())))
+            .await
+            .unwrap();
+        client
+            .set_resource(SetResourceRequest::new(resource))
+            .await
+            .unwrap();
+    }
+
+    // desks are exclusive per user in acme only
+    let desk = |id: &str| {
+        Reservation::new(
+            "yuzhe",
+            id,
+            "2023-01-09T10:10:10-0800".parse().unwrap(),
+            "2023-01-10T10:10:10-0800".parse().unwrap(),
+            "",
+        )
+    };
+    for id in ["desk-1", "desk-2"] {
+        client.reserve(ReserveRequest::new(desk(id))).await.unwrap();
+    }
+    client
+        .reserve(acme(ReserveRequest::new(desk("desk-1"))))
+        .await
+        .unwrap();
+    let err = client
+        .reserve(acme(ReserveRequest::new(desk("desk-2"))))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::AlreadyExists);
+}
+
+#[tokio::test]
+async fn grpc_availability_should_use_the_request_tenant() {
+    let config = TestConfig::with_server_port(50024);
+    let mut client = get_test_cliet(&config).await;
+
+    let resource = Resource {
+        // monday
+        opening_hours: vec![OpeningHours {
+            weekday: 1,
+            open: "09:00".to_string(),
+            close: "17:00".to_string(),
+        }],
+        ..Resource::new("test-office")
+    };
+    client
+        .set_resource(SetResourceRequest::new(resource))
+        .await
+        .unwrap();
+
+    // acme doesn't know the office, so it is free the whole week
+    let request = AvailabilityRequest::new(
+        "test-office",
+        "2023-01-01T00:00:00Z".parse().unwrap(),
+        "2023-01-08T00:00:00Z".parse().unwrap(),
+    );
+    let ret = client
+        .availability(acme(request.clone()))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(ret.windows.len(), 1);
+    assert_eq!(ret.windows[0].start, request.start);
+    assert_eq!(ret.windows[0].end, request.end);
+
+    let ret = client.availability(request).await.unwrap().into_inner();
+    assert_eq!(
+        ret.windows[0].start,
+        Some("2023-01-02T09:00:00Z".parse().unwrap())
+    );
+}
+
+#[tokio::test]
+async fn grpc_retried_reserve_should_not_duplicate() {
+    let config = TestConfig::with_server_port(50014);
+    let mut client = get_test_cliet(&config).await;
+
+    let rsvp = Reservation::new(
+        "yuzhe",
+        "test-rid",
+        "2023-01-09T10:10:10-0800".parse().unwrap(),
+        "2023-01-10T10:10:10-0800".parse().unwrap(),
+        "retry",
+    );
+    let with_key = |rsvp: Reservation, key: &str| {
+        let mut request = ReserveRequest::new(rsvp).into_request();
+        request
+            .metadata_mut()
+            .insert("idempotency-key", key.parse().unwrap());
+        request
+    };
+
+    let first = client
+        .reserve(with_key(rsvp.clone(), "retry-1"))
+        .await
+        .unwrap()
+        .into_inner();
+    // the retry gets the first answer instead of a conflict with itself
+    let second = client
+        .reserve(with_key(rsvp.clone(), "retry-1"))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(first, second);
+
+    let other = Reservation {
+        note: "another booking".to_string(),
+        ..rsvp
+    };
+    let err = client
+        .reserve(with_key(other, "retry-1"))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn grpc_update_attendees_should_work() {
+    let config = TestConfig::with_server_port(50015);
+    let mut client = get_test_cliet(&config).await;
+    make_reservations(&mut client, 1).await;
+
+    let ret = client
+        .update_attendees(UpdateAttendeesRequest::new(
+            1,
+            "yuzhe",
+            vec![Attendee::new("tyr", AttendeeRole::Required)],
+            vec![],
+        ))
+        .await
+        .unwrap()
+        .into_inner()
+        .reservation
+        .unwrap();
+    assert_eq!(ret.attendees.len(), 1);
+
+    let filter = ReservationFilterBuilder::default()
+        .user_id("tyr")
+        .build()
+        .unwrap();
+    let ret = client
+        .filter(FilterRequest::new(filter))
+        .await
+        .unwrap()
+        .into_inner()
+        .reservations;
+    assert_eq!(ret.len(), 1);
+
+    let err = client
+        .update_attendees(UpdateAttendeesRequest::new(1, "tyr", vec![], vec![]))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::PermissionDenied);
+}
+
 #[tokio::test]
 async fn grpc_get_should_work() {
     let config = TestConfig::with_server_port(50004);
@@ -137,8 +757,12 @@ async fn grpc_update_should_work() {
     let mut client = get_test_cliet(&config).await;
     make_reservations(&mut client, 10).await;
 
+    let rsvp = Reservation {
+        note: "test-node-6-updated".to_string(),
+        ..Default::default()
+    };
     let ret = client
-        .update(UpdateRequest::new(6, "test-node-6-updated".to_string()))
+        .update(UpdateRequest::new(6, rsvp, &["note"]))
         .await
         .unwrap()
         .into_inner()
@@ -149,6 +773,99 @@ async fn grpc_update_should_work() {
     assert_eq!(ret.resource_id, "test-rid-6");
     assert_eq!(ret.status, abi::ReservationStatus::Pending as i32);
     assert_eq!(ret.note, "test-node-6-updated");
+    assert_eq!(ret.version, 2);
+
+    // a write based on an older version is aborted
+    let err = client
+        .update(UpdateRequest::new(6, Reservation::default(), &["note"]).with_version(1))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::Aborted);
+
+    // several fields at once
+    let rsvp = Reservation::new(
+        "yuzhe-2",
+        "test-rid-11",
+        "2023-01-11T10:10:10-0800".parse().unwrap(),
+        "2023-01-12T10:10:10-0800".parse().unwrap(),
+        "",
+    );
+    let ret = client
+        .update(UpdateRequest::new(
+            6,
+            rsvp.clone(),
+            &["user_id", "resource_id", "start", "end"],
+        ))
+        .await
+        .unwrap()
+        .into_inner()
+        .reservation
+        .unwrap();
+    assert_eq!(ret.user_id, "yuzhe-2");
+    assert_eq!(ret.resource_id, "test-rid-11");
+    assert_eq!(ret.start, rsvp.start);
+    assert_eq!(ret.end, rsvp.end);
+    assert_eq!(ret.note, "test-node-6-updated");
+
+    // moving onto another booking should fail
+    let rsvp = Reservation {
+        resource_id: "test-rid-1".to_string(),
+        start: Some("2023-01-09T10:10:10-0800".parse().unwrap()),
+        ..Default::default()
+    };
+    let ret = client
+        .update(UpdateRequest::new(6, rsvp, &["resource_id", "start"]))
+        .await;
+    assert_eq!(ret.unwrap_err().code(), tonic::Code::AlreadyExists);
+}
+
+#[tokio::test]
+async fn grpc_reschedule_should_work() {
+    let config = TestConfig::with_server_port(50007);
+    let mut client = get_test_cliet(&config).await;
+    make_reservations(&mut client, 2).await;
+
+    let start = "2023-01-11T10:10:10-0800"
+        .parse::<prost_types::Timestamp>()
+        .unwrap();
+    let end = "2023-01-12T10:10:10-0800"
+        .parse::<prost_types::Timestamp>()
+        .unwrap();
+    let ret = client
+        .reschedule(RescheduleRequest::new(
+            1,
+            start.clone(),
+            end.clone(),
+            Some("test-rid-2".to_string()),
+        ))
+        .await
+        .unwrap()
+        .into_inner()
+        .reservation
+        .unwrap();
+    assert_eq!(ret.resource_id, "test-rid-2");
+    assert_eq!(ret.start, Some(start));
+    assert_eq!(ret.end, Some(end));
+
+    // moving onto the other booking of test-rid-2 should fail and keep the original
+    let ret = client
+        .reschedule(RescheduleRequest::new(
+            1,
+            "2023-01-09T10:10:10-0800".parse().unwrap(),
+            "2023-01-10T10:10:10-0800".parse().unwrap(),
+            None,
+        ))
+        .await;
+    assert_eq!(ret.unwrap_err().code(), tonic::Code::AlreadyExists);
+
+    let ret = client
+        .get(GetRequest::new(1))
+        .await
+        .unwrap()
+        .into_inner()
+        .reservation
+        .unwrap();
+    assert_eq!(ret.resource_id, "test-rid-2");
 }
 
 #[tokio::test]
@@ -180,6 +897,80 @@ async fn grpc_filter_should_work() {
     assert_eq!(pager.next, Some(19));
 }
 
+#[tokio::test]
+async fn grpc_check_should_report_conflicts_without_reserving() {
+    let config = TestConfig::with_server_port(50016);
+    let mut client = get_test_cliet(&config).await;
+    make_reservations(&mut client, 1).await;
+
+    let mut rsvp = Reservation::new(
+        "tyr",
+        "test-rid-1",
+        "2023-01-10T09:10:10-0800".parse().unwrap(),
+        "2023-01-10T12:10:10-0800".parse().unwrap(),
+        "",
+    );
+    let ret = client
+        .check(ReserveRequest::new(rsvp.clone()))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(ret.conflicts.len(), 1);
+    assert_eq!(
+        ret.conflicts[0].existing.as_ref().unwrap().reservation_id,
+        1
+    );
+
+    rsvp.resource_id = "test-rid-2".to_string();
+    let ret = client
+        .check(ReserveRequest::new(rsvp.clone()))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(ret.conflicts.is_empty());
+    assert_eq!(ret.reservation.unwrap().id, 0);
+
+    // nothing was reserved, so the same window is still free
+    let ret = client
+        .reserve(ReserveRequest::new(rsvp))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(ret.reservation.unwrap().resource_id, "test-rid-2");
+}
+
+#[tokio::test]
+async fn grpc_reserve_conflict_should_suggest_alternatives() {
+    let config = TestConfig::with_server_port(50017);
+    let mut client = get_test_cliet(&config).await;
+    make_reservations(&mut client, 1).await;
+
+    let rsvp = Reservation::new(
+        "tyr",
+        "test-rid-1",
+        "2023-01-10T09:10:10-0800".parse().unwrap(),
+        "2023-01-10T12:10:10-0800".parse().unwrap(),
+        "",
+    );
+    let err = client
+        .reserve(ReserveRequest::new(rsvp).with_suggestions(2))
+        .await
+        .unwrap_err();
+    let details = ConflictDetails::from_status(&err).unwrap();
+    let suggestions = &details.conflicts[0].suggestions;
+    assert_eq!(suggestions.len(), 2);
+    // right after the existing reservation, then right before it
+    assert_eq!(
+        suggestions[0].start,
+        Some("2023-01-10T10:10:10-0800".parse().unwrap())
+    );
+    assert_eq!(
+        suggestions[1].end,
+        Some("2023-01-09T10:10:10-0800".parse().unwrap())
+    );
+    assert!(suggestions.iter().all(|s| s.resource_id == "test-rid-1"));
+}
+
 async fn get_test_cliet(
     config: &TestConfig,
 ) -> ReservationServiceClient<tonic::transport::Channel> {
@@ -215,10 +1006,10 @@ async fn make_reservations(
     for i in 0..count {
         let mut rsvp = Reservation::new(
             "yuzhe",
-            &format!("test-rid-{}", i + 1),
+            format!("test-rid-{}", i + 1),
             "2023-01-09T10:10:10-0800".parse().unwrap(),
             "2023-01-10T10:10:10-0800".parse().unwrap(),
-            &format!("test-node-{}", i + 1),
+            format!("test-node-{}", i + 1),
         );
         let ret = client
             .reserve(ReserveRequest::new(rsvp.clone()))
@@ -228,6 +1019,32 @@ async fn make_reservations(
             .reservation
             .unwrap();
         rsvp.id = ret.id;
+        rsvp.created_at = ret.created_at.clone();
+        rsvp.updated_at = ret.updated_at.clone();
+        rsvp.version = ret.version;
+        rsvp.time_zone = ret.time_zone.clone();
         assert_eq!(ret, rsvp);
     }
 }
+
+/// send the request as tenant acme
+fn acme<T>(message: T) -> tonic::Request<T> {
+    let mut request = message.into_request();
+    request
+        .metadata_mut()
+        .insert("x-tenant-id", "acme".parse().unwrap());
+    request
+}
+
+async fn get_reservation(
+    client: &mut ReservationServiceClient<tonic::transport::Channel>,
+    id: i64,
+) -> Reservation {
+    client
+        .get(GetRequest::new(id))
+        .await
+        .unwrap()
+        .into_inner()
+        .reservation
+        .unwrap()
+}